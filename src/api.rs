@@ -1,147 +1,685 @@
-use crate::types::{Account, CollectionInformation, CollectionRewards, CollectionStats, Network, Order};
-use thiserror::Error;
-use ethers::{
-    prelude::Address, 
+use crate::cache::Cache;
+use crate::constants;
+use crate::rate_limit::RateLimiter;
+#[cfg(feature = "metrics")]
+use crate::telemetry;
+use crate::types::{
+    Account,
+    CollectionInformation,
+    CollectionRewardPoints,
+    CollectionRewards,
+    CollectionStats,
+    Event,
+    EventType,
+    Network,
+    Order,
+    RewardsEpoch,
+    RewardsProof,
+    UserRewards,
 };
-use reqwest::{Client, ClientBuilder};
+use thiserror::Error;
+use ethers_core::types::Address;
+use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport, TransportError};
+#[cfg(not(target_arch = "wasm32"))]
+use futures::future::BoxFuture;
+#[cfg(target_arch = "wasm32")]
+use futures::future::LocalBoxFuture;
+use futures::future::{FutureExt, Shared, TryFutureExt};
+use futures_timer::Delay;
+use instant::Instant;
+use reqwest::{header::RETRY_AFTER, Client, StatusCode, Url};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{field, Instrument, Span};
 
 pub struct LooksRareApi {
-    client: Client,
+    transport: Arc<dyn Transport>,
     network: Network,
+    cache: Option<Arc<Cache>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    in_flight: Mutex<HashMap<String, Pending>>,
 }
 
 impl LooksRareApi {
     pub fn new() -> Self {
-        let builder = ClientBuilder::new();
+        Self::builder().build()
+    }
 
-        let client = builder.build().unwrap();
+    pub fn builder() -> LooksRareApiBuilder {
+        LooksRareApiBuilder::default()
+    }
 
-        Self {
-            client,
-            network: Network::Mainnet,
-        }
+    /// The response cache, if one was configured.
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_deref()
+    }
+
+    /// Client for the v2 API sharing this client's transport, network and
+    /// rate limiter.
+    pub fn v2(&self) -> crate::v2::api::LooksRareApi {
+        crate::v2::api::LooksRareApi::from_parts(self.transport.clone(), self.network.clone(), self.rate_limiter.clone())
     }
 
     pub async fn get_account(&self, address: Address) -> Result<Account, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/accounts", api);
-        let mut map = std::collections::HashMap::new();
-        map.insert("address", serde_json::to_value(address)?);
+        self.get(Endpoint::Accounts, &address_query(address)?, |text| parse_account(text, address)).await
+    }
 
-        let res = self.client.get(url).query(&map).send().await?;
-        let text = res.text().await?;
-        let resp: AccountResponse = serde_json::from_str(&text)?;
-        let data: Account = resp.data.ok_or(LooksRareApiError::AccountNotFound {
-            address: address
-        })?;
+    pub async fn get_orders(&self, req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        self.get(Endpoint::Orders, &orders_query(req)?, parse_orders).await
+    }
 
-        Ok(data)
+    /// Every order matching `req`, following the cursor page by page.
+    ///
+    /// Pages hold `req.pagination.first` orders, or as many as the API
+    /// allows when unset; a cursor in `req` is the starting point.
+    pub async fn get_all_orders(&self, mut req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        let pagination = req.pagination.take();
+        let first = pagination.as_ref().and_then(|p| p.first).unwrap_or(constants::MAX_ORDERS_PAGE_SIZE);
+        let mut cursor = pagination.and_then(|p| p.cursor);
+
+        let mut orders = vec![];
+        loop {
+            req.pagination = Some(Pagination { first: Some(first), cursor: cursor.take() });
+            let page = self.get_orders(req.clone()).await?;
+            let last_page = (page.len() as u64) < first;
+
+            cursor = page.last().map(|order| order.hash.clone());
+            orders.extend(page);
+
+            if last_page || cursor.is_none() {
+                return Ok(orders);
+            }
+        }
     }
 
-    pub async fn get_orders(&self, req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/orders", api);
-
-
-        let mut query = vec![];
-
-        if let Some(_a) = &req.is_order_ask { query.push(("isOrderAsk", serde_json::to_value(req.is_order_ask)?)); };
-        if let Some(_b) = &req.collection { query.push(("collection", serde_json::to_value(req.collection)?)); };
-        if let Some(_c) = &req.token_id { query.push(("tokenId", serde_json::to_value(req.token_id.unwrap().to_string())?)); };
-        if let Some(_d) = &req.signer { query.push(("signer", serde_json::to_value(req.signer)?)); };
-        if let Some(_e) = &req.nonce { query.push(("nonce", serde_json::to_value(req.nonce.unwrap().to_string())?)); };
-        if let Some(_f) = &req.strategy { query.push(("strategy", serde_json::to_value(req.strategy)?)); };
-        if let Some(_g) = &req.currency { query.push(("currency", serde_json::to_value(req.currency)?)); };
-        if let Some(_h) = &req.price {
-            if let Some(_min) = &req.price.clone().unwrap().min { query.push(("price[min]", serde_json::to_value(req.price.clone().unwrap().min.unwrap().to_string())?)); };
-            if let Some(_max) = &req.price.clone().unwrap().max { query.push(("price[max]", serde_json::to_value(req.price.clone().unwrap().max.unwrap().to_string())?)); };
+    pub async fn get_events(&self, req: EventsRequest) -> Result<Vec<Event>, LooksRareApiError> {
+        self.get(Endpoint::Events, &events_query(req)?, parse_events).await
+    }
+
+    pub async fn create_order(&self, req: CreateOrderRequest) -> Result<Order, LooksRareApiError> {
+        let url = request_url(&format!("{}/orders", self.network.api()), &[])?;
+        let request = HttpRequest::post_json(url, &req)?;
+        let span = request_span(&request);
+
+        let result = send(self.transport.as_ref(), self.rate_limiter.as_deref(), request)
+            .instrument(span.clone())
+            .await
+            .and_then(|res| parse_created_order(&res.body));
+        if let Err(e) = &result {
+            span.record("error", field::display(e));
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            telemetry::record_error(&format!("{}/orders", self.network.api()), e);
+        }
+
+        result
+    }
+
+    pub async fn get_nonce(&self, address: Address) -> Result<u64, LooksRareApiError> {
+        self.get(Endpoint::OrdersNonce, &address_query(address)?, |text| parse_nonce(text, address)).await
+    }
+
+    pub async fn get_collection_information(&self, address:Address) -> Result<CollectionInformation, LooksRareApiError> {
+        self.get(Endpoint::Collections, &address_query(address)?, |text| parse_collection_information(text, address)).await
+    }
+
+    pub async fn get_collection_stats(&self, address:Address) -> Result<CollectionStats, LooksRareApiError> {
+        self.get(Endpoint::CollectionStats, &address_query(address)?, |text| parse_collection_stats(text, address)).await
+    }
+
+    pub async fn get_top_5_listing_rewards_collections(&self) -> Result<Vec<CollectionRewards>, LooksRareApiError> {
+        self.get(Endpoint::ListingRewards, &[], parse_top_5_listing_rewards_collections).await
+    }
+
+    pub async fn get_user_rewards(&self, address: Address) -> Result<UserRewards, LooksRareApiError> {
+        self.get(Endpoint::Rewards, &address_query(address)?, |text| parse_user_rewards(text, address)).await
+    }
+
+    pub async fn get_collection_reward_points(&self, address: Address) -> Result<Vec<CollectionRewardPoints>, LooksRareApiError> {
+        self.get(Endpoint::RewardsCollections, &address_query(address)?, |text| parse_collection_reward_points(text, address)).await
+    }
+
+    pub async fn get_rewards_epochs(&self) -> Result<Vec<RewardsEpoch>, LooksRareApiError> {
+        self.get(Endpoint::RewardsEpochs, &[], parse_rewards_epochs).await
+    }
+
+    pub async fn get_rewards_proof(&self, address: Address) -> Result<RewardsProof, LooksRareApiError> {
+        self.get(Endpoint::RewardsProof, &address_query(address)?, |text| parse_rewards_proof(text, address)).await
+    }
+
+    /// Sends a GET request and parses the response with `parse`, serving it
+    /// from the cache when possible.
+    async fn get<T>(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, serde_json::Value)],
+        parse: impl FnOnce(&str) -> Result<T, LooksRareApiError>,
+    ) -> Result<T, LooksRareApiError> {
+        let url = request_url(&format!("{}{}", self.network.api(), endpoint.path()), query)?;
+        let key = url.to_string();
+        let request = HttpRequest::get(url);
+        let span = request_span(&request);
+        let start = Instant::now();
+
+        let mut status = None;
+        let result = match self.cache.as_ref().and_then(|cache| cache.get(endpoint, &key)) {
+            Some(text) => {
+                span.record("cached", true);
+                // Only successful responses are cached.
+                status = Some(StatusCode::OK);
+                parse(&text)
+            }
+            None => self.get_shared(key.clone(), request, &span).await.and_then(|res| {
+                status = Some(res.status);
+                let value = parse(&res.body)?;
+                // Only answers that parse are kept, so a transient "not found"
+                // is not served for the endpoint's whole TTL.
+                if let (true, Some(cache)) = (res.status.is_success(), &self.cache) {
+                    cache.insert(endpoint, key, res.body);
+                }
+                Ok(value)
+            }),
         };
-        if let Some(_i) = &req.start_time { query.push(("startTime", serde_json::to_value(req.start_time.unwrap().to_string())?)); };
-        if let Some(_j) = &req.end_time { query.push(("endTime", serde_json::to_value(req.end_time.unwrap().to_string())?)); };
-        if let Some(_k) = &req.status { req.status.unwrap().iter_mut().for_each(|x| { query.push(("status[]", serde_json::to_value(x.to_str()).unwrap())) } ); };
-        if let Some(_l) = &req.pagination {
-            if let Some(_first) = &req.pagination.clone().unwrap().first { query.push(("pagination[first]", serde_json::to_value(req.pagination.clone().unwrap().first.unwrap().to_string())?)); };    
-            if let Some(_cursor) = &req.pagination.clone().unwrap().cursor { query.push(("pagination[cursor]", serde_json::to_value(req.pagination.clone().unwrap().cursor)?)); }; 
+        record_outcome(&span, start.elapsed(), status, &result);
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            telemetry::record_error(&format!("{}{}", self.network.api(), endpoint.path()), e);
+        }
+
+        result
+    }
+
+    /// Sends a GET request, sharing the response with identical requests
+    /// already in flight.
+    async fn get_shared(&self, key: String, request: HttpRequest, span: &Span) -> Result<HttpResponse, LooksRareApiError> {
+        let request = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let pending = in_flight
+                .entry(key.clone())
+                .and_modify(|_| {
+                    span.record("coalesced", true);
+                })
+                .or_insert_with(|| {
+                    let req = Fetch {
+                        transport: self.transport.clone(),
+                        request,
+                        limiter: self.rate_limiter.clone(),
+                    };
+                    Pending {
+                        request: share(fetch(req).instrument(span.clone())),
+                        waiters: 0,
+                    }
+                });
+            pending.waiters += 1;
+            pending.request.clone()
         };
-        if let Some(_m) = &req.sort { query.push(("sort", serde_json::to_value(req.sort.unwrap().to_str())?)); };
 
-        let res = self.client.get(url).query(&query).send().await?;
-        let text = res.text().await?;
+        let waiter = Waiter {
+            in_flight: &self.in_flight,
+            key,
+        };
+        let result = request.await;
+        // Let go of the shared request first, so a caller nobody joined gets
+        // the error itself rather than a shared one.
+        drop(waiter);
 
-        let resp: OrdersResponse = serde_json::from_str(&text)?;
-        let data: Vec<Order> = resp.data.ok_or(LooksRareApiError::OrdersNotFound)?;
+        result.map_err(|e| Arc::try_unwrap(e).unwrap_or_else(LooksRareApiError::Shared))
+    }
+}
+
+/// A request in flight and the number of callers waiting on it.
+struct Pending {
+    request: InFlight,
+    waiters: usize,
+}
+
+/// A caller waiting on a [`Pending`] request. Dropping the last one removes
+/// the request, even if every caller gave up before it finished.
+struct Waiter<'a> {
+    in_flight: &'a Mutex<HashMap<String, Pending>>,
+    key: String,
+}
 
-        Ok(data)
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(pending) = in_flight.get_mut(&self.key) {
+            pending.waiters -= 1;
+            if pending.waiters == 0 {
+                in_flight.remove(&self.key);
+            }
+        }
     }
+}
 
-    pub async fn get_nonce(&self, address: Address) -> Result<u64, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/orders/nonce", api);
+// Request building and response parsing, shared with `crate::blocking`.
 
-        let mut query = vec![];
-        query.push(("address", serde_json::to_value(address)?));
+pub(crate) type Query = Vec<(&'static str, serde_json::Value)>;
 
-        let res = self.client.get(url).query(&query).send().await?;
-        let text = res.text().await?;
+pub(crate) fn address_query(address: Address) -> Result<Query, LooksRareApiError> {
+    Ok(vec![("address", serde_json::to_value(address)?)])
+}
 
-        let resp: NonceResponse = serde_json::from_str(&text)?;
-        let nonce_string: String = resp.data.ok_or(LooksRareApiError::NonceNotFound {
-            address: address
-        })?;
-        let nonce: u64 = nonce_string.parse().unwrap();
+pub(crate) fn orders_query(req: OrdersRequest) -> Result<Query, LooksRareApiError> {
+    let mut query = vec![];
+
+    if let Some(_a) = &req.is_order_ask { query.push(("isOrderAsk", serde_json::to_value(req.is_order_ask)?)); };
+    if let Some(_b) = &req.collection { query.push(("collection", serde_json::to_value(req.collection)?)); };
+    if let Some(_c) = &req.token_id { query.push(("tokenId", serde_json::to_value(req.token_id.unwrap().to_string())?)); };
+    if let Some(_d) = &req.signer { query.push(("signer", serde_json::to_value(req.signer)?)); };
+    if let Some(_e) = &req.nonce { query.push(("nonce", serde_json::to_value(req.nonce.unwrap().to_string())?)); };
+    if let Some(_f) = &req.strategy { query.push(("strategy", serde_json::to_value(req.strategy)?)); };
+    if let Some(_g) = &req.currency { query.push(("currency", serde_json::to_value(req.currency)?)); };
+    if let Some(_h) = &req.price {
+        if let Some(_min) = &req.price.clone().unwrap().min { query.push(("price[min]", serde_json::to_value(req.price.clone().unwrap().min.unwrap().to_string())?)); };
+        if let Some(_max) = &req.price.clone().unwrap().max { query.push(("price[max]", serde_json::to_value(req.price.clone().unwrap().max.unwrap().to_string())?)); };
+    };
+    if let Some(_i) = &req.start_time { query.push(("startTime", serde_json::to_value(req.start_time.unwrap().to_string())?)); };
+    if let Some(_j) = &req.end_time { query.push(("endTime", serde_json::to_value(req.end_time.unwrap().to_string())?)); };
+    if let Some(_k) = &req.status { req.status.unwrap().iter_mut().for_each(|x| { query.push(("status[]", serde_json::to_value(x.to_str()).unwrap())) } ); };
+    if let Some(_l) = &req.pagination {
+        if let Some(_first) = &req.pagination.clone().unwrap().first { query.push(("pagination[first]", serde_json::to_value(req.pagination.clone().unwrap().first.unwrap().to_string())?)); };    
+        if let Some(_cursor) = &req.pagination.clone().unwrap().cursor { query.push(("pagination[cursor]", serde_json::to_value(req.pagination.clone().unwrap().cursor)?)); }; 
+    };
+    if let Some(_m) = &req.sort { query.push(("sort", serde_json::to_value(req.sort.unwrap().to_str())?)); };
+
+    Ok(query)
+}
 
-        Ok(nonce)
+pub(crate) fn events_query(req: EventsRequest) -> Result<Query, LooksRareApiError> {
+    let mut query = vec![];
+
+    if let Some(collection) = req.collection { query.push(("collection", serde_json::to_value(collection)?)); }
+    if let Some(token_id) = req.token_id { query.push(("tokenId", serde_json::to_value(token_id.to_string())?)); }
+    if let Some(from) = req.from { query.push(("from", serde_json::to_value(from)?)); }
+    if let Some(to) = req.to { query.push(("to", serde_json::to_value(to)?)); }
+    if let Some(type_) = req.type_ { query.push(("type", serde_json::to_value(type_.to_str())?)); }
+    if let Some(pagination) = req.pagination {
+        if let Some(first) = pagination.first { query.push(("pagination[first]", serde_json::to_value(first.to_string())?)); }
+        if let Some(cursor) = pagination.cursor { query.push(("pagination[cursor]", serde_json::to_value(cursor)?)); }
     }
 
-    pub async fn get_collection_information(&self, address:Address) -> Result<CollectionInformation, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/collections", api);
+    Ok(query)
+}
+
+pub(crate) fn parse_account(text: &str, address: Address) -> Result<Account, LooksRareApiError> {
+    let resp: AccountResponse = serde_json::from_str(text)?;
+    let data: Account = resp.data.ok_or(LooksRareApiError::AccountNotFound {
+        address: address
+    })?;
+
+    Ok(data)
+}
+
+pub(crate) fn parse_orders(text: &str) -> Result<Vec<Order>, LooksRareApiError> {
+    let resp: OrdersResponse = serde_json::from_str(text)?;
+    let data: Vec<Order> = resp.data.ok_or(LooksRareApiError::OrdersNotFound)?;
+
+    Ok(data)
+}
+
+pub(crate) fn parse_events(text: &str) -> Result<Vec<Event>, LooksRareApiError> {
+    let resp: EventsResponse = serde_json::from_str(text)?;
+    let data: Vec<Event> = resp.data.ok_or(LooksRareApiError::EventsNotFound)?;
+
+    Ok(data)
+}
+
+pub(crate) fn parse_created_order(text: &str) -> Result<Order, LooksRareApiError> {
+    let resp: CreateOrderResponse = serde_json::from_str(text)?;
+    let order: Order = resp.data.ok_or(LooksRareApiError::OrderRejected {
+        message: resp.message.unwrap_or_default(),
+    })?;
+
+    Ok(order)
+}
+
+pub(crate) fn parse_nonce(text: &str, address: Address) -> Result<u64, LooksRareApiError> {
+    let resp: NonceResponse = serde_json::from_str(text)?;
+    let nonce_string: String = resp.data.ok_or(LooksRareApiError::NonceNotFound {
+        address: address
+    })?;
+    let nonce: u64 = nonce_string.parse().unwrap();
+
+    Ok(nonce)
+}
+
+pub(crate) fn parse_collection_information(text: &str, address: Address) -> Result<CollectionInformation, LooksRareApiError> {
+    let resp: CollectionInformationResponse = serde_json::from_str(text)?;
+    let collection_information: CollectionInformation = resp.data.ok_or(LooksRareApiError::CollectionNotFound{
+        address: address
+    })?;
+
+    Ok(collection_information)
+}
+
+pub(crate) fn parse_collection_stats(text: &str, address: Address) -> Result<CollectionStats, LooksRareApiError> {
+    let resp: CollectionStatsResponse = serde_json::from_str(text)?;
+    let collection_stats: CollectionStats = resp.data.ok_or(LooksRareApiError::CollectionNotFound {
+        address: address
+    })?;
+
+    Ok(collection_stats)
+}
+
+pub(crate) fn parse_top_5_listing_rewards_collections(text: &str) -> Result<Vec<CollectionRewards>, LooksRareApiError> {
+    let resp: Top5ListingRewardsCollectionsResponse = serde_json::from_str(text)?;
+    let top_5_listing_rewards_collections: Vec<CollectionRewards> = resp.data;
+
+    Ok(top_5_listing_rewards_collections)
+}
+
+pub(crate) fn parse_user_rewards(text: &str, address: Address) -> Result<UserRewards, LooksRareApiError> {
+    let resp: UserRewardsResponse = serde_json::from_str(text)?;
+    let user_rewards: UserRewards = resp.data.ok_or(LooksRareApiError::RewardsNotFound {
+        address
+    })?;
+
+    Ok(user_rewards)
+}
 
-        let mut query = vec![];
-        query.push(("address", serde_json::to_value(address)?));
+pub(crate) fn parse_collection_reward_points(text: &str, address: Address) -> Result<Vec<CollectionRewardPoints>, LooksRareApiError> {
+    let resp: CollectionRewardPointsResponse = serde_json::from_str(text)?;
+    let collection_reward_points: Vec<CollectionRewardPoints> = resp.data.ok_or(LooksRareApiError::RewardsNotFound {
+        address
+    })?;
 
-        let res = self.client.get(url).query(&query).send().await?;
-        let text = res.text().await?;
+    Ok(collection_reward_points)
+}
+
+pub(crate) fn parse_rewards_epochs(text: &str) -> Result<Vec<RewardsEpoch>, LooksRareApiError> {
+    let resp: RewardsEpochsResponse = serde_json::from_str(text)?;
+    let rewards_epochs: Vec<RewardsEpoch> = resp.data;
+
+    Ok(rewards_epochs)
+}
 
-        let resp: CollectionInformationResponse = serde_json::from_str(&text)?;
-        let collection_information: CollectionInformation = resp.data.ok_or(LooksRareApiError::CollectionNotFound{
-            address: address
-        })?;
+pub(crate) fn parse_rewards_proof(text: &str, address: Address) -> Result<RewardsProof, LooksRareApiError> {
+    let resp: RewardsProofResponse = serde_json::from_str(text)?;
+    let rewards_proof: RewardsProof = resp.data.ok_or(LooksRareApiError::RewardsNotFound {
+        address
+    })?;
 
-        Ok(collection_information)
+    Ok(rewards_proof)
+}
+
+/// `url` with `query` appended, strings unquoted.
+pub(crate) fn request_url(url: &str, query: &[(&str, serde_json::Value)]) -> Result<Url, LooksRareApiError> {
+    let mut url = Url::parse(url)?;
+    if !query.is_empty() {
+        let mut pairs = url.query_pairs_mut();
+        for (key, value) in query {
+            match value {
+                serde_json::Value::String(value) => pairs.append_pair(key, value),
+                value => pairs.append_pair(key, &value.to_string()),
+            };
+        }
     }
 
-    pub async fn get_collection_stats(&self, address:Address) -> Result<CollectionStats, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/collections/stats", api);
+    Ok(url)
+}
 
-        let mut query = vec![];
-        query.push(("address", serde_json::to_value(address)?));
+#[cfg(not(target_arch = "wasm32"))]
+type InFlight = Shared<BoxFuture<'static, Result<HttpResponse, Arc<LooksRareApiError>>>>;
+#[cfg(target_arch = "wasm32")]
+type InFlight = Shared<LocalBoxFuture<'static, Result<HttpResponse, Arc<LooksRareApiError>>>>;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn share(fetch: impl Future<Output = Result<HttpResponse, LooksRareApiError>> + Send + 'static) -> InFlight {
+    fetch.map_err(Arc::new).boxed().shared()
+}
 
-        let res = self.client.get(url).query(&query).send().await?;
-        let text = res.text().await?;
-        let resp: CollectionStatsResponse = serde_json::from_str(&text)?;
-        let collection_stats: CollectionStats = resp.data.ok_or(LooksRareApiError::CollectionNotFound {
-            address: address
-        })?;
+#[cfg(target_arch = "wasm32")]
+fn share(fetch: impl Future<Output = Result<HttpResponse, LooksRareApiError>> + 'static) -> InFlight {
+    fetch.map_err(Arc::new).boxed_local().shared()
+}
+
+/// Everything a GET request needs once it is detached from the client.
+struct Fetch {
+    transport: Arc<dyn Transport>,
+    request: HttpRequest,
+    limiter: Option<Arc<RateLimiter>>,
+}
 
-        Ok(collection_stats)
+async fn fetch(req: Fetch) -> Result<HttpResponse, LooksRareApiError> {
+    send(req.transport.as_ref(), req.limiter.as_deref(), req.request).await
+}
+
+/// Span covering one API request. `send` and `record_outcome` record the
+/// outcome on it.
+pub(crate) fn request_span(request: &HttpRequest) -> Span {
+    tracing::info_span!(
+        "looksrare_request",
+        method = %request.method,
+        endpoint = request.url.path(),
+        query = %redacted_query(&request.url),
+        status = field::Empty,
+        latency_ms = field::Empty,
+        retries = field::Empty,
+        rate_limit_wait_ms = field::Empty,
+        cached = field::Empty,
+        coalesced = field::Empty,
+        error = field::Empty,
+    )
+}
+
+/// Records how a request ended on its span.
+///
+/// `send` records the same fields on the span of the call that actually
+/// sent the request; this covers the ones served from the cache or joined
+/// to another call's request, and errors raised while parsing the response.
+pub(crate) fn record_outcome<T>(
+    span: &Span,
+    latency: Duration,
+    status: Option<StatusCode>,
+    result: &Result<T, LooksRareApiError>,
+) {
+    span.record("latency_ms", latency.as_millis() as u64);
+    if let Some(status) = status {
+        span.record("status", status.as_u16());
+    }
+    if let Err(e) = result {
+        span.record("error", field::display(e));
     }
+}
 
-    pub async fn get_top_5_listing_rewards_collections(&self) -> Result<Vec<CollectionRewards>, LooksRareApiError> {
-        let api = self.network.api();
-        let url = format!("{}/collections/listing-rewards", api);
+/// Query parameters that carry credentials.
+const REDACTED_PARAMS: &[&str] = &["apiKey", "api_key", "key"];
+
+/// The query string of `url` with credential values replaced.
+fn redacted_query(url: &Url) -> String {
+    url.query_pairs()
+        .map(|(key, value)| {
+            let value = if REDACTED_PARAMS.contains(&key.as_ref()) { "[redacted]".into() } else { value };
+            format!("{}={}", key, value)
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Sends `request` through `transport`, waiting for the rate limiter and
+/// retrying while the API answers `429 Too Many Requests`.
+pub(crate) async fn send(
+    transport: &dyn Transport,
+    limiter: Option<&RateLimiter>,
+    request: HttpRequest,
+) -> Result<HttpResponse, LooksRareApiError> {
+    let span = Span::current();
+    #[cfg(feature = "metrics")]
+    let (method, endpoint) = (request.method.clone(), request.url.path().to_string());
+    let start = Instant::now();
+    let mut stats = SendStats::default();
+
+    let result = retry(transport, limiter, request, &mut stats).await;
+    let latency = start.elapsed();
+
+    span.record("latency_ms", latency.as_millis() as u64);
+    span.record("retries", stats.retries);
+    span.record("rate_limit_wait_ms", stats.rate_limit_wait.as_millis() as u64);
+    match &result {
+        Ok(res) => span.record("status", res.status.as_u16()),
+        Err(e) => span.record("error", field::display(e)),
+    };
+
+    #[cfg(feature = "metrics")]
+    telemetry::record_request(&method, &endpoint, &result, latency, &stats);
+
+    result
+}
+
+/// How much work `send` needed beyond a single round trip.
+#[derive(Default)]
+pub(crate) struct SendStats {
+    pub(crate) retries: u32,
+    pub(crate) rate_limit_wait: Duration,
+}
+
+async fn retry(
+    transport: &dyn Transport,
+    limiter: Option<&RateLimiter>,
+    request: HttpRequest,
+    stats: &mut SendStats,
+) -> Result<HttpResponse, LooksRareApiError> {
+    let limiter = match limiter {
+        Some(limiter) => limiter,
+        None => return Ok(transport.send(request).await?),
+    };
+
+    loop {
+        stats.rate_limit_wait += limiter.acquire().await;
+        let res = transport.send(request.clone()).await?;
+
+        if res.status != StatusCode::TOO_MANY_REQUESTS || stats.retries == limiter.retries() {
+            return Ok(res);
+        }
+
+        stats.retries += 1;
+        let retry_after = res
+            .headers
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs);
+        Delay::new(retry_after.unwrap_or_else(|| limiter.backoff(stats.retries))).await;
+    }
+}
+
+/// The GET endpoints of the v1 API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Accounts,
+    Orders,
+    OrdersNonce,
+    Events,
+    Collections,
+    CollectionStats,
+    ListingRewards,
+    Rewards,
+    RewardsCollections,
+    RewardsEpochs,
+    RewardsProof,
+}
+
+impl Endpoint {
+    pub fn path(&self) -> &'static str {
+        match self {
+            Endpoint::Accounts => "/accounts",
+            Endpoint::Orders => "/orders",
+            Endpoint::OrdersNonce => "/orders/nonce",
+            Endpoint::Events => "/events",
+            Endpoint::Collections => "/collections",
+            Endpoint::CollectionStats => "/collections/stats",
+            Endpoint::ListingRewards => "/collections/listing-rewards",
+            Endpoint::Rewards => "/rewards",
+            Endpoint::RewardsCollections => "/rewards/collections",
+            Endpoint::RewardsEpochs => "/rewards/epochs",
+            Endpoint::RewardsProof => "/rewards/proof",
+        }
+    }
+}
+
+pub struct LooksRareApiBuilder {
+    transport: Option<Arc<dyn Transport>>,
+    network: Network,
+    cache: Option<Cache>,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl fmt::Debug for LooksRareApiBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LooksRareApiBuilder")
+            .field("custom_transport", &self.transport.is_some())
+            .field("network", &self.network)
+            .field("cache", &self.cache.is_some())
+            .field("rate_limiter", &self.rate_limiter)
+            .finish()
+    }
+}
+
+impl Default for LooksRareApiBuilder {
+    fn default() -> Self {
+        Self {
+            transport: None,
+            network: Network::Mainnet,
+            cache: None,
+            rate_limiter: None,
+        }
+    }
+}
+
+impl LooksRareApiBuilder {
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Sends requests to `url` instead of the public API.
+    pub fn base_url(self, url: impl Into<String>) -> Self {
+        self.network(Network::Custom(url.into()))
+    }
+
+    pub fn client(self, client: Client) -> Self {
+        self.transport(ReqwestTransport::new(client))
+    }
+
+    /// Sends requests through `transport` instead of a `reqwest::Client`.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
 
-        let res = self.client.get(url).send().await?;
-        let text = res.text().await?;
-        println!("{}",text);
-        let resp: Top5ListingRewardsCollectionsResponse = serde_json::from_str(&text)?;
-        let top_5_listing_rewards_collections: Vec<CollectionRewards> = resp.data;
+    /// Caches responses according to `cache`'s per-endpoint TTLs.
+    pub fn cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
 
-        Ok(top_5_listing_rewards_collections)
+    /// Paces every request through `rate_limiter`.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
     }
 
+    pub fn build(self) -> LooksRareApi {
+        let transport = self
+            .transport
+            .unwrap_or_else(|| Arc::new(ReqwestTransport::default()));
+
+        LooksRareApi {
+            transport,
+            network: self.network,
+            cache: self.cache.map(Arc::new),
+            rate_limiter: self.rate_limiter.map(Arc::new),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -180,6 +718,52 @@ struct OrdersResponse {
     data: Option<Vec<Order>>,
 }
 
+/// Filters for `GET /events`. The cursor is the id of the last event seen;
+/// pages go from newest to oldest.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EventsRequest {
+    pub collection: Option<Address>,
+    pub token_id: Option<u64>,
+    pub from: Option<Address>,
+    pub to: Option<Address>,
+    pub type_: Option<EventType>,
+    pub pagination: Option<Pagination>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EventsResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<Vec<Event>>,
+}
+
+/// A signed maker order as submitted to `POST /orders`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderRequest {
+    pub signature: String,
+    pub collection: Address,
+    pub token_id: String,
+    pub signer: Address,
+    pub strategy: Address,
+    pub currency: Address,
+    pub is_order_ask: bool,
+    pub nonce: String,
+    pub amount: String,
+    pub price: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub min_percentage_to_ask: u64,
+    pub params: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CreateOrderResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<Order>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct NonceResponse {
     success: bool,
@@ -208,6 +792,34 @@ struct Top5ListingRewardsCollectionsResponse {
     data: Vec<CollectionRewards>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct UserRewardsResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<UserRewards>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CollectionRewardPointsResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<Vec<CollectionRewardPoints>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RewardsEpochsResponse {
+    success: bool,
+    message: Option<String>,
+    data: Vec<RewardsEpoch>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct RewardsProofResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<RewardsProof>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Pagination {
     pub first: Option<u64>,
@@ -220,14 +832,58 @@ pub enum LooksRareApiError {
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    /// An error from a custom [`Transport`].
+    #[error(transparent)]
+    Transport(TransportError),
     #[error("Account not found (address: {address}")]
     AccountNotFound { address: Address },
     #[error("Orders not found")]
     OrdersNotFound,
+    #[error("Events not found")]
+    EventsNotFound,
     #[error("Nonce not found (address: {address}")]
     NonceNotFound { address: Address },
     #[error("Collection not found (address: {address}")]
     CollectionNotFound { address: Address },
+    #[error("Rewards not found (address: {address}")]
+    RewardsNotFound { address: Address },
+    #[error("Order rejected: {message}")]
+    OrderRejected { message: String },
+    /// The error of a request shared by several identical concurrent calls.
+    #[error(transparent)]
+    Shared(Arc<LooksRareApiError>),
+}
+
+#[cfg(feature = "metrics")]
+impl LooksRareApiError {
+    /// Variant name, used as the `error` label of request metrics.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            LooksRareApiError::Reqwest(_) => "reqwest",
+            LooksRareApiError::SerdeJson(_) => "serde_json",
+            LooksRareApiError::Url(_) => "url",
+            LooksRareApiError::Transport(_) => "transport",
+            LooksRareApiError::AccountNotFound { .. } => "account_not_found",
+            LooksRareApiError::OrdersNotFound => "orders_not_found",
+            LooksRareApiError::EventsNotFound => "events_not_found",
+            LooksRareApiError::NonceNotFound { .. } => "nonce_not_found",
+            LooksRareApiError::CollectionNotFound { .. } => "collection_not_found",
+            LooksRareApiError::RewardsNotFound { .. } => "rewards_not_found",
+            LooksRareApiError::OrderRejected { .. } => "order_rejected",
+            LooksRareApiError::Shared(e) => e.kind(),
+        }
+    }
+}
+
+impl From<TransportError> for LooksRareApiError {
+    fn from(e: TransportError) -> Self {
+        match e.downcast::<reqwest::Error>() {
+            Ok(e) => LooksRareApiError::Reqwest(*e),
+            Err(e) => LooksRareApiError::Transport(e),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -258,7 +914,7 @@ pub enum Sort {
 }
 
 impl Sort {
-    fn to_str(&self) -> &str {
+    pub(crate) fn to_str(&self) -> &str {
         match &self {
             Sort::ExpiringSoon => "EXPIRING_SOON",
             Sort::Newest => "NEWEST",
@@ -277,6 +933,479 @@ pub struct Price {
 
 #[cfg(test)]
 mod tests {
+    use crate::cache::CacheMetrics;
+    use crate::test_utils::{api, fixture, mock_get};
+    use crate::types::Account;
+    use ethers_core::types::U256;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock,
+        MockServer,
+        ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_account() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/accounts", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/accounts").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let account: Account = api.get_account(input_address).await.unwrap();
+        let output_address: Address = account.address;
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn can_get_orders() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[
+            ("isOrderAsk", "true"),
+            ("collection", "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258"),
+            ("tokenId", "62962"),
+            ("signer", "0x9e69b59b8d2a094cb1117f92ff7dcf51ed467b41"),
+            ("nonce", "17832"),
+            ("strategy", "0x579af6fd30bf83a5ac0d636bc619f98dbdeb930c"),
+            ("currency", "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+            ("price[min]", "12000000000000000000000000000000000000"),
+            ("price[max]", "13000000000000000000000000000000000000"),
+            ("startTime", "1667747434"),
+            ("endTime", "1667754634"),
+            ("status[]", "CANCELLED"),
+            ("status[]", "EXPIRED"),
+            ("pagination[first]", "4"),
+            ("sort", "NEWEST"),
+        ], "v1/orders").await;
+        let api = api(&server);
+
+        let req = OrdersRequest {
+            is_order_ask: Some(true),
+            collection: Some("0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap()),
+            token_id: Some(62962),
+            signer: Some("0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap()),
+            nonce: Some(17832),
+            strategy: Some("0x579af6fd30bf83a5ac0d636bc619f98dbdeb930c".parse().unwrap()),
+            currency: Some("0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".parse().unwrap()),
+            price: Some(Price{
+                min: Some(12000000000000000000000000000000000000),
+                max: Some(13000000000000000000000000000000000000),
+            }),
+            start_time: Some(1667747434),
+            end_time: Some(1667754634),
+            status: Some(vec![Status::Cancelled,Status::Expired]),
+            pagination: Some(Pagination {
+                first: Some(4),
+                cursor: None,
+            }),
+            sort: Some(Sort::Newest),
+        };
+
+        let input_status: Vec<Status> = req.clone().status.unwrap();
+
+        let orders: Vec<Order> = api.get_orders(req).await.unwrap();
+        let first_order: Order = orders.into_iter().next().unwrap();
+
+        assert_eq!(first_order.nonce, "17832");
+        assert_eq!(first_order.token_id, "62962");
+        assert!(input_status.iter().any(|i| i.to_str() == first_order.status));
+    }
+
+    #[tokio::test]
+    async fn orders_pagination() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[
+            ("pagination[first]", "4"),
+            ("pagination[cursor]", "0xd12240238374bbb1b23078fc71feeffa1d6c54b81888dfc5d9ea54d17c6a30a7"),
+        ], "v1/orders_page").await;
+        let api = api(&server);
+
+        let req = OrdersRequest {
+            is_order_ask: None,
+            collection: None,
+            token_id: None,
+            signer: None,
+            nonce: None,
+            strategy: None,
+            currency: None,
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: None,
+            pagination: Some(Pagination {
+                first: Some(4),
+                cursor: Some(String::from("0xd12240238374bbb1b23078fc71feeffa1d6c54b81888dfc5d9ea54d17c6a30a7")),
+            }),
+            sort: None,
+        };
+
+        let orders: Vec<Order> = api.get_orders(req).await.unwrap();
+
+        assert_eq!(orders.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn follows_cursor_through_all_pages() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[
+            ("pagination[first]", "4"),
+            ("pagination[cursor]", "0x00000000000000000000000000000000000000000000000000000000000000a3"),
+        ], "v1/orders").await;
+        mock_get(&server, "/api/v1/orders", &[("pagination[first]", "4")], "v1/orders_page").await;
+        let api = api(&server);
+
+        let req = OrdersRequest {
+            is_order_ask: None,
+            collection: None,
+            token_id: None,
+            signer: None,
+            nonce: None,
+            strategy: None,
+            currency: None,
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: None,
+            pagination: Some(Pagination { first: Some(4), cursor: None }),
+            sort: None,
+        };
+
+        let orders: Vec<Order> = api.get_all_orders(req).await.unwrap();
+
+        assert_eq!(orders.len(), 5);
+        assert_eq!(orders[4].token_id, "62962");
+    }
+
+    #[tokio::test]
+    async fn can_create_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v1/orders"))
+            .and(body_partial_json(serde_json::json!({
+                "collection": "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258",
+                "tokenId": "62962",
+                "nonce": "17832",
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "message": null,
+                "data": fixture("v1/orders")["data"][0],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let api = api(&server);
+
+        let req = CreateOrderRequest {
+            signature: format!("0x{}", "11".repeat(65)),
+            collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+            token_id: String::from("62962"),
+            signer: "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap(),
+            strategy: "0x579af6fd30bf83a5ac0d636bc619f98dbdeb930c".parse().unwrap(),
+            currency: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".parse().unwrap(),
+            is_order_ask: true,
+            nonce: String::from("17832"),
+            amount: String::from("1"),
+            price: String::from("12500000000000000000000000000000000000"),
+            start_time: 1667747434,
+            end_time: 1667754634,
+            min_percentage_to_ask: 8500,
+            params: String::new(),
+        };
+
+        let order: Order = api.create_order(req).await.unwrap();
+        assert_eq!(order.nonce, "17832");
+    }
+
+    #[tokio::test]
+    async fn can_get_nonce() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders/nonce", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/orders_nonce").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonce: u64 = api.get_nonce(input_address).await.unwrap();
+        assert_eq!(nonce, 17833);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_information() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections", &[("address", "0x1a92f7381b9f03921564a437210bb9396471050c")], "v1/collections").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let collection_information: CollectionInformation = api.get_collection_information(input_address).await.unwrap();
+        let output_address: Address = collection_information.address;
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_stats() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections/stats", &[("address", "0x1a92f7381b9f03921564a437210bb9396471050c")], "v1/collections_stats").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let collection_stats: CollectionStats = api.get_collection_stats(input_address).await.unwrap();
+        let output_address: Address = collection_stats.address;
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn caches_collection_information() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections", &[("address", "0x1a92f7381b9f03921564a437210bb9396471050c")], "v1/collections").await;
+        let api = LooksRareApi::builder().base_url(server.uri()).cache(Cache::new()).build();
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let first: CollectionInformation = api.get_collection_information(input_address).await.unwrap();
+        let second: CollectionInformation = api.get_collection_information(input_address).await.unwrap();
+
+        assert_eq!(first.name, second.name);
+        assert_eq!(api.cache().unwrap().metrics(), CacheMetrics { hits: 1, misses: 1 });
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_missing_data() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "message": null, "data": null })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let api = LooksRareApi::builder().base_url(server.uri()).cache(Cache::new()).build();
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        for _ in 0..2 {
+            let result = api.get_collection_information(input_address).await;
+            assert!(matches!(result, Err(LooksRareApiError::CollectionNotFound { .. })));
+        }
+
+        assert_eq!(api.cache().unwrap().metrics(), CacheMetrics { hits: 0, misses: 2 });
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_orders() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture("v1/orders_page")))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let api = LooksRareApi::builder().base_url(server.uri()).cache(Cache::new()).build();
+
+        let req = OrdersRequest {
+            is_order_ask: None,
+            collection: None,
+            token_id: None,
+            signer: None,
+            nonce: None,
+            strategy: None,
+            currency: None,
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: None,
+            pagination: None,
+            sort: None,
+        };
+
+        api.get_orders(req.clone()).await.unwrap();
+        api.get_orders(req).await.unwrap();
+
+        assert_eq!(api.cache().unwrap().metrics(), CacheMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn coalesces_concurrent_identical_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(fixture("v1/collections_stats"))
+                .set_delay(std::time::Duration::from_millis(100)))
+            // The eight concurrent calls, then the later one.
+            .expect(2)
+            .mount(&server)
+            .await;
+        let api = api(&server);
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let results = futures::future::join_all((0..8).map(|_| api.get_collection_stats(input_address))).await;
+        api.get_collection_stats(input_address).await.unwrap();
+
+        assert!(results.iter().all(|r| r.as_ref().unwrap().address == input_address));
+    }
+
+    #[tokio::test]
+    async fn forgets_requests_every_caller_gave_up_on() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .respond_with(ResponseTemplate::new(200)
+                .set_body_json(fixture("v1/collections_stats"))
+                .set_delay(std::time::Duration::from_millis(200)))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let api = api(&server);
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let abandoned = tokio::time::timeout(std::time::Duration::from_millis(50), api.get_collection_stats(input_address)).await;
+        assert!(abandoned.is_err());
+
+        // Sent anew rather than joined to the abandoned request.
+        api.get_collection_stats(input_address).await.unwrap();
+    }
+
+    struct FailingTransport;
+
+    #[async_trait::async_trait]
+    impl Transport for FailingTransport {
+        async fn send(&self, _req: HttpRequest) -> Result<HttpResponse, TransportError> {
+            Err("connection refused".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_the_error_of_a_request_nobody_joined() {
+        let api = LooksRareApi::builder().transport(FailingTransport).build();
+
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let result = api.get_collection_stats(input_address).await;
+
+        assert!(matches!(result, Err(LooksRareApiError::Transport(_))));
+    }
+
+    /// Replays a recorded response and remembers every request it saw.
+    #[derive(Default)]
+    struct ReplayTransport {
+        requests: Mutex<Vec<HttpRequest>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for ReplayTransport {
+        async fn send(&self, req: HttpRequest) -> Result<HttpResponse, TransportError> {
+            self.requests.lock().unwrap().push(req);
+
+            Ok(HttpResponse {
+                status: StatusCode::OK,
+                headers: Default::default(),
+                body: fixture("v1/orders_nonce").to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_requests_through_custom_transport() {
+        let transport = Arc::new(ReplayTransport::default());
+        let api = LooksRareApi::builder().transport(transport.clone()).build();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonce: u64 = api.get_nonce(input_address).await.unwrap();
+        assert_eq!(nonce, 17833);
+
+        let requests = transport.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, reqwest::Method::GET);
+        assert_eq!(
+            requests[0].url.as_str(),
+            "https://api.looksrare.org/api/v1/orders/nonce?address=0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f"
+        );
+    }
+
+    #[test]
+    fn redacts_credentials_from_traced_queries() {
+        let url = Url::parse("https://api.looksrare.org/api/v1/orders?collection=0xabc&apiKey=secret").unwrap();
+
+        assert_eq!(redacted_query(&url), "collection=0xabc&apiKey=[redacted]");
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limited_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orders/nonce"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        mock_get(&server, "/api/v1/orders/nonce", &[], "v1/orders_nonce").await;
+        let api = LooksRareApi::builder()
+            .base_url(server.uri())
+            .rate_limiter(RateLimiter::per_second(50))
+            .build();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonce: u64 = api.get_nonce(input_address).await.unwrap();
+        assert_eq!(nonce, 17833);
+    }
+
+    #[tokio::test]
+    async fn can_get_top_5_listing_rewards_collections() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections/listing-rewards", &[], "v1/collections_listing_rewards").await;
+        let api = api(&server);
+
+        let top_5_listing_rewards_collections: Vec<CollectionRewards> = api.get_top_5_listing_rewards_collections().await.unwrap();
+        assert_eq!(top_5_listing_rewards_collections.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn can_get_user_rewards() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let user_rewards: UserRewards = api.get_user_rewards(input_address).await.unwrap();
+        assert_eq!(user_rewards.address, input_address);
+        assert_eq!(user_rewards.trading.pending, U256::from_dec_str("1250000000000000000").unwrap());
+        assert_eq!(user_rewards.listing.claimed, U256::from_dec_str("4100000000000000000").unwrap());
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_reward_points() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/collections", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards_collections").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let collection_reward_points: Vec<CollectionRewardPoints> = api.get_collection_reward_points(input_address).await.unwrap();
+        assert_eq!(collection_reward_points.len(), 2);
+        assert_eq!(collection_reward_points[0].points, U256::from(1820));
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_epochs() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/epochs", &[], "v1/rewards_epochs").await;
+        let api = api(&server);
+
+        let rewards_epochs: Vec<RewardsEpoch> = api.get_rewards_epochs().await.unwrap();
+        assert_eq!(rewards_epochs.iter().map(|e| e.epoch).collect::<Vec<_>>(), vec![41, 42]);
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_proof() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/proof", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards_proof").await;
+        let api = api(&server);
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let rewards_proof: RewardsProof = api.get_rewards_proof(input_address).await.unwrap();
+        assert_eq!(rewards_proof.address, input_address);
+        assert_eq!(rewards_proof.proof.len(), 3);
+    }
+}
+
+#[cfg(all(test, feature = "live-tests"))]
+mod live_tests {
     use crate::types::Account;
 
     use super::*;
@@ -427,8 +1556,52 @@ mod tests {
         let top_5_listing_rewards_collections: Vec<CollectionRewards> = api.get_top_5_listing_rewards_collections().await.unwrap();
 
         let num_of_collections: usize = top_5_listing_rewards_collections.len();
-        println!("{}", num_of_collections);
         assert_eq!(num_of_collections, 5);
     }
-}
 
+    #[tokio::test]
+    async fn can_get_user_rewards() {
+        let api = LooksRareApi::new();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+
+        let user_rewards: UserRewards = api.get_user_rewards(input_address).await.unwrap();
+
+        let output_address: Address = user_rewards.address;
+
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_reward_points() {
+        let api = LooksRareApi::new();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+
+        let collection_reward_points: Vec<CollectionRewardPoints> = api.get_collection_reward_points(input_address).await.unwrap();
+
+        assert!(collection_reward_points.iter().all(|c| c.collection != Address::zero()));
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_epochs() {
+        let api = LooksRareApi::new();
+
+        let rewards_epochs: Vec<RewardsEpoch> = api.get_rewards_epochs().await.unwrap();
+
+        assert!(rewards_epochs.windows(2).all(|w| w[0].epoch != w[1].epoch));
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_proof() {
+        let api = LooksRareApi::new();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+
+        let rewards_proof: RewardsProof = api.get_rewards_proof(input_address).await.unwrap();
+
+        let output_address: Address = rewards_proof.address;
+
+        assert_eq!(input_address, output_address);
+    }
+}
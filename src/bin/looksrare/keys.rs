@@ -0,0 +1,35 @@
+use clap::Args;
+use looksrare::keys::{SigningKey, SigningKeyError};
+use std::path::PathBuf;
+
+/// Where the signing key comes from. Secrets are read from the environment
+/// rather than flags so they stay out of shell history.
+#[derive(Args)]
+pub struct SignerArgs {
+    /// Encrypted JSON keystore.
+    #[arg(long, required_unless_present_any = ["private_key_env", "private_key_file"])]
+    keystore: Option<PathBuf>,
+
+    /// Environment variable holding the keystore password.
+    #[arg(long, default_value = "LOOKSRARE_KEYSTORE_PASSWORD")]
+    password_env: String,
+
+    /// Environment variable holding a hex-encoded private key.
+    #[arg(long, conflicts_with_all = ["keystore", "private_key_file"])]
+    private_key_env: Option<String>,
+
+    /// File holding a hex-encoded private key.
+    #[arg(long, conflicts_with = "keystore")]
+    private_key_file: Option<PathBuf>,
+}
+
+impl SignerArgs {
+    pub fn load(&self) -> Result<SigningKey, SigningKeyError> {
+        match (&self.keystore, &self.private_key_env, &self.private_key_file) {
+            (Some(path), _, _) => SigningKey::from_keystore_env(path, &self.password_env),
+            (None, Some(name), _) => SigningKey::from_env(name),
+            (None, None, Some(path)) => SigningKey::from_file(path),
+            (None, None, None) => unreachable!("clap requires a key source"),
+        }
+    }
+}
@@ -0,0 +1,446 @@
+//! Command-line access to the LooksRare API.
+
+mod keys;
+mod output;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ethers::{
+    prelude::{Address, Http, Middleware, Provider, Signer, SignerMiddleware},
+    types::{transaction::eip712::EIP712Domain, U256},
+    utils::parse_ether,
+};
+use keys::SignerArgs;
+use looksrare::{
+    api::{LooksRareApi, OrdersRequest, Pagination, Price, Sort, Status},
+    constants,
+    contracts::LooksRareExchange,
+    keys::SigningKey,
+    signing::{domain, sign_maker_order},
+    simulation::Simulate,
+    types::{MakerOrder, Order},
+};
+use output::Format;
+use serde::Serialize;
+use std::error::Error;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Parser)]
+#[command(name = "looksrare", version, about = "Query the LooksRare API")]
+struct Cli {
+    /// Output format.
+    #[arg(long, short, value_enum, global = true, default_value_t = Format::Table)]
+    format: Format,
+
+    /// Use a custom API deployment instead of mainnet. Orders are still signed
+    /// for the exchange given by `--chain-id` and `--exchange`.
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Account information.
+    Account {
+        #[arg(value_parser = parse_address)]
+        address: Address,
+    },
+    /// Orders matching the given filters.
+    Orders(OrdersArgs),
+    /// Next order nonce of an address.
+    Nonce {
+        #[arg(value_parser = parse_address)]
+        address: Address,
+    },
+    /// Collection information and stats.
+    #[command(subcommand)]
+    Collection(CollectionCommand),
+    /// Trading and listing rewards of an address.
+    Rewards {
+        #[arg(value_parser = parse_address)]
+        address: Address,
+    },
+    /// Sign and submit an ask for a token; prints the order hash.
+    List(ListArgs),
+    /// Sign and submit a bid for a token, or for any token of the collection
+    /// without `--token-id`; prints the order hash.
+    Offer(OfferArgs),
+    /// Cancel orders on-chain; prints the transaction hash.
+    Cancel(CancelArgs),
+}
+
+#[derive(Args)]
+struct ListArgs {
+    #[arg(long)]
+    token_id: String,
+    #[command(flatten)]
+    order: OrderArgs,
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+#[derive(Args)]
+struct OfferArgs {
+    #[arg(long)]
+    token_id: Option<String>,
+    #[command(flatten)]
+    order: OrderArgs,
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+#[derive(Args)]
+struct OrderArgs {
+    #[arg(long, value_parser = parse_address)]
+    collection: Address,
+    /// Price in ETH.
+    #[arg(long)]
+    price: String,
+    /// Number of tokens (ERC-1155).
+    #[arg(long, default_value_t = 1)]
+    amount: u64,
+    /// Seconds until the order expires.
+    #[arg(long, default_value_t = 7 * 24 * 60 * 60)]
+    duration: u64,
+    /// Lowest share of the price, in basis points, the seller accepts after fees.
+    #[arg(long, default_value_t = 8500)]
+    min_percentage_to_ask: u64,
+    /// Standard sale strategy, used for orders on one token.
+    #[arg(long, value_parser = parse_address)]
+    strategy_standard_sale: Option<Address>,
+    /// Collection sale strategy, used for offers on any token.
+    #[arg(long, value_parser = parse_address)]
+    strategy_collection_sale: Option<Address>,
+    /// WETH, the currency orders are priced in.
+    #[arg(long, value_parser = parse_address)]
+    weth: Option<Address>,
+    #[command(flatten)]
+    exchange: ExchangeArgs,
+}
+
+/// The exchange orders are signed for and cancellations are sent to.
+///
+/// Contract addresses default to the mainnet deployment on chain 1 and must
+/// all be given on any other chain.
+#[derive(Args)]
+struct ExchangeArgs {
+    /// Chain the exchange is deployed on.
+    #[arg(long, default_value_t = 1)]
+    chain_id: u64,
+    /// `LooksRareExchange` address.
+    #[arg(long, value_parser = parse_address)]
+    exchange: Option<Address>,
+}
+
+impl ExchangeArgs {
+    /// `address`, defaulting to `mainnet` on chain 1. `flag` names the
+    /// option in the error.
+    fn deployed(&self, address: Option<Address>, mainnet: &str, flag: &str) -> Result<Address, Box<dyn Error>> {
+        match address {
+            Some(address) => Ok(address),
+            None if self.chain_id == 1 => Ok(mainnet.parse().unwrap()),
+            None => Err(format!("--{} is required on chain {}", flag, self.chain_id).into()),
+        }
+    }
+
+    fn exchange(&self) -> Result<Address, Box<dyn Error>> {
+        self.deployed(self.exchange, constants::LOOKSRARE_EXCHANGE_MAINNET, "exchange")
+    }
+
+    fn domain(&self) -> Result<EIP712Domain, Box<dyn Error>> {
+        Ok(domain(self.chain_id, self.exchange()?))
+    }
+}
+
+#[derive(Args)]
+struct CancelArgs {
+    /// JSON-RPC endpoint used to send the cancellation.
+    #[arg(long, env = "ETH_RPC_URL")]
+    rpc_url: String,
+    /// Nonces of the orders to cancel. May be repeated.
+    #[arg(long = "nonce", required_unless_present = "all_below")]
+    nonces: Vec<u64>,
+    /// Cancel every order with a nonce below this one.
+    #[arg(long, conflicts_with = "nonces")]
+    all_below: Option<u64>,
+    #[command(flatten)]
+    exchange: ExchangeArgs,
+    #[command(flatten)]
+    signer: SignerArgs,
+}
+
+#[derive(Subcommand)]
+enum CollectionCommand {
+    Info {
+        #[arg(value_parser = parse_address)]
+        address: Address,
+    },
+    Stats {
+        #[arg(value_parser = parse_address)]
+        address: Address,
+    },
+}
+
+#[derive(Args)]
+struct OrdersArgs {
+    /// Only asks (`true`) or only bids (`false`).
+    #[arg(long)]
+    is_order_ask: Option<bool>,
+    #[arg(long, value_parser = parse_address)]
+    collection: Option<Address>,
+    #[arg(long)]
+    token_id: Option<u64>,
+    #[arg(long, value_parser = parse_address)]
+    signer: Option<Address>,
+    #[arg(long)]
+    nonce: Option<u64>,
+    #[arg(long, value_parser = parse_address)]
+    strategy: Option<Address>,
+    #[arg(long, value_parser = parse_address)]
+    currency: Option<Address>,
+    /// Minimum price in wei.
+    #[arg(long)]
+    price_min: Option<u128>,
+    /// Maximum price in wei.
+    #[arg(long)]
+    price_max: Option<u128>,
+    #[arg(long)]
+    start_time: Option<u64>,
+    #[arg(long)]
+    end_time: Option<u64>,
+    /// May be repeated.
+    #[arg(long, value_enum)]
+    status: Vec<StatusArg>,
+    /// Page size.
+    #[arg(long)]
+    first: Option<u64>,
+    /// Hash of the last order of the previous page.
+    #[arg(long)]
+    cursor: Option<String>,
+    #[arg(long, value_enum)]
+    sort: Option<SortArg>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum StatusArg {
+    Cancelled,
+    Executed,
+    Expired,
+    Valid,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    ExpiringSoon,
+    Newest,
+    PriceAsc,
+    PriceDesc,
+}
+
+impl From<StatusArg> for Status {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Cancelled => Status::Cancelled,
+            StatusArg::Executed => Status::Executed,
+            StatusArg::Expired => Status::Expired,
+            StatusArg::Valid => Status::Valid,
+        }
+    }
+}
+
+impl From<SortArg> for Sort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::ExpiringSoon => Sort::ExpiringSoon,
+            SortArg::Newest => Sort::Newest,
+            SortArg::PriceAsc => Sort::PriceAsc,
+            SortArg::PriceDesc => Sort::PriceDesc,
+        }
+    }
+}
+
+impl From<OrdersArgs> for OrdersRequest {
+    fn from(args: OrdersArgs) -> Self {
+        let price = (args.price_min.is_some() || args.price_max.is_some()).then_some(Price {
+            min: args.price_min,
+            max: args.price_max,
+        });
+        let pagination = (args.first.is_some() || args.cursor.is_some()).then_some(Pagination {
+            first: args.first,
+            cursor: args.cursor,
+        });
+
+        OrdersRequest {
+            is_order_ask: args.is_order_ask,
+            collection: args.collection,
+            token_id: args.token_id,
+            signer: args.signer,
+            nonce: args.nonce,
+            strategy: args.strategy,
+            currency: args.currency,
+            price,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            status: (!args.status.is_empty()).then(|| args.status.into_iter().map(Status::from).collect()),
+            pagination,
+            sort: args.sort.map(Sort::from),
+        }
+    }
+}
+
+fn parse_address(s: &str) -> Result<Address, String> {
+    s.parse().map_err(|_| format!("invalid address: {}", s))
+}
+
+fn parse_token_id(s: &str) -> Result<U256, Box<dyn Error>> {
+    U256::from_dec_str(s).map_err(|_| format!("invalid token id: {}", s).into())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let mut builder = LooksRareApi::builder();
+    if let Some(base_url) = cli.base_url {
+        builder = builder.base_url(base_url);
+    }
+    let api = builder.build();
+
+    match run(&api, cli.command, cli.format).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(api: &LooksRareApi, command: Command, format: Format) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Account { address } => print(format, &api.get_account(address).await?),
+        Command::Orders(args) => print(format, &api.get_orders(args.into()).await?),
+        Command::Nonce { address } => print(format, &api.get_nonce(address).await?),
+        Command::Collection(CollectionCommand::Info { address }) => {
+            print(format, &api.get_collection_information(address).await?)
+        }
+        Command::Collection(CollectionCommand::Stats { address }) => {
+            print(format, &api.get_collection_stats(address).await?)
+        }
+        Command::Rewards { address } => print(format, &api.get_user_rewards(address).await?),
+        Command::List(args) => {
+            let token_id = parse_token_id(&args.token_id)?;
+            let order = submit(api, &args.signer.load()?, true, Some(token_id), args.order).await?;
+            println!("{}", order.hash);
+            Ok(())
+        }
+        Command::Offer(args) => {
+            let token_id = args.token_id.as_deref().map(parse_token_id).transpose()?;
+            let order = submit(api, &args.signer.load()?, false, token_id, args.order).await?;
+            println!("{}", order.hash);
+            Ok(())
+        }
+        Command::Cancel(args) => cancel(args.signer.load()?, args).await,
+    }
+}
+
+/// Signs a maker order priced in WETH with the signer's next nonce and
+/// submits it. Offers without a token id use the collection strategy.
+async fn submit(
+    api: &LooksRareApi,
+    signer: &SigningKey,
+    is_order_ask: bool,
+    token_id: Option<U256>,
+    args: OrderArgs,
+) -> Result<Order, Box<dyn Error>> {
+    let exchange = &args.exchange;
+    let strategy = match token_id {
+        Some(_) => exchange.deployed(
+            args.strategy_standard_sale,
+            constants::STRATEGY_STANDARD_SALE_MAINNET,
+            "strategy-standard-sale",
+        )?,
+        None => exchange.deployed(
+            args.strategy_collection_sale,
+            constants::STRATEGY_COLLECTION_SALE_MAINNET,
+            "strategy-collection-sale",
+        )?,
+    };
+    let currency = exchange.deployed(args.weth, constants::WETH_MAINNET, "weth")?;
+    let domain = exchange.domain()?;
+
+    let nonce = api.get_nonce(signer.address()).await?;
+    let start_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let order = MakerOrder {
+        is_order_ask,
+        signer: signer.address(),
+        collection: args.collection,
+        price: parse_ether(&args.price)?,
+        token_id: token_id.unwrap_or_default(),
+        amount: U256::from(args.amount),
+        strategy,
+        currency,
+        nonce: U256::from(nonce),
+        start_time,
+        end_time: start_time + args.duration,
+        min_percentage_to_ask: args.min_percentage_to_ask,
+        params: Default::default(),
+    };
+    let signature = sign_maker_order(signer, &order, domain).await?;
+
+    Ok(api.create_order(order.into_request(signature)).await?)
+}
+
+async fn cancel(signer: SigningKey, args: CancelArgs) -> Result<(), Box<dyn Error>> {
+    let address = args.exchange.exchange()?;
+    let provider = Provider::<Http>::try_from(args.rpc_url.as_str())?;
+    let chain_id = provider.get_chainid().await?.as_u64();
+    if chain_id != args.exchange.chain_id {
+        return Err(format!(
+            "the RPC endpoint is on chain {}, not {}; pass --chain-id and --exchange to cancel there",
+            chain_id, args.exchange.chain_id
+        )
+        .into());
+    }
+    let client = SignerMiddleware::new(provider, signer.with_chain_id(chain_id));
+    let exchange = LooksRareExchange::new(address, Arc::new(client));
+
+    let call = match args.all_below {
+        Some(min_nonce) => exchange.cancel_all_orders_for_sender(U256::from(min_nonce)),
+        None => exchange.cancel_multiple_maker_orders(args.nonces.into_iter().map(U256::from).collect()),
+    };
+    call.simulate().await?;
+    let pending = call.send().await?;
+
+    println!("{:?}", pending.tx_hash());
+    Ok(())
+}
+
+fn print(format: Format, value: &impl Serialize) -> Result<(), Box<dyn Error>> {
+    println!("{}", output::render(format, value)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use looksrare::signing::mainnet_domain;
+
+    #[test]
+    fn defaults_to_mainnet_contracts_only_on_mainnet() {
+        let mainnet = ExchangeArgs { chain_id: 1, exchange: None };
+        assert_eq!(mainnet.domain().unwrap(), mainnet_domain());
+
+        let goerli = ExchangeArgs { chain_id: 5, exchange: None };
+        assert!(goerli.exchange().is_err());
+        assert!(goerli.deployed(None, constants::WETH_MAINNET, "weth").is_err());
+
+        let exchange = Address::repeat_byte(1);
+        let goerli = ExchangeArgs { chain_id: 5, exchange: Some(exchange) };
+        assert_eq!(goerli.domain().unwrap(), domain(5, exchange));
+    }
+}
@@ -0,0 +1,270 @@
+use clap::ValueEnum;
+use serde::{
+    de::{Deserializer, MapAccess, SeqAccess, Visitor},
+    Deserialize, Serialize, Serializer,
+};
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+/// Renders an API response. Tables and CSV get one row per array element,
+/// with nested objects flattened into `parent.child` columns, in the order
+/// the fields are serialized.
+pub fn render(format: Format, value: &impl Serialize) -> serde_json::Result<String> {
+    Ok(match format {
+        Format::Json => serde_json::to_string_pretty(value)?,
+        Format::Table => table(&rows(&ordered(value)?)),
+        Format::Csv => csv(&rows(&ordered(value)?)),
+    })
+}
+
+/// A JSON value whose objects keep their keys in document order.
+///
+/// `serde_json::Map` sorts keys unless serde_json's `preserve_order` feature
+/// is on, and turning that on here would change it for every crate linked
+/// with the library too.
+enum Node {
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+    Scalar(Value),
+}
+
+/// Reads `value` back from its JSON text, which lists struct fields in
+/// declaration order.
+fn ordered(value: &impl Serialize) -> serde_json::Result<Node> {
+    serde_json::from_str(&serde_json::to_string(value)?)
+}
+
+impl<'de> Deserialize<'de> for Node {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(NodeVisitor)
+    }
+}
+
+struct NodeVisitor;
+
+impl<'de> Visitor<'de> for NodeVisitor {
+    type Value = Node;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a JSON value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::from(v)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(Node::Scalar(Value::Null))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Node, A::Error> {
+        let mut items = vec![];
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Node::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Node, A::Error> {
+        let mut fields = vec![];
+        while let Some(field) = map.next_entry()? {
+            fields.push(field);
+        }
+        Ok(Node::Object(fields))
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::Object(fields) => serializer.collect_map(fields.iter().map(|(key, value)| (key, value))),
+            Node::Array(items) => serializer.collect_seq(items),
+            Node::Scalar(value) => value.serialize(serializer),
+        }
+    }
+}
+
+struct Rows {
+    columns: Vec<String>,
+    cells: Vec<Vec<String>>,
+}
+
+fn rows(node: &Node) -> Rows {
+    let records: Vec<Vec<(String, &Node)>> = match node {
+        Node::Array(items) => items.iter().map(record).collect(),
+        other => vec![record(other)],
+    };
+
+    let mut columns: Vec<String> = vec![];
+    for record in &records {
+        for (key, _) in record {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let cells = records
+        .iter()
+        .map(|record| {
+            columns
+                .iter()
+                .map(|column| {
+                    record
+                        .iter()
+                        .find(|(key, _)| key == column)
+                        .map(|(_, node)| cell(node))
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+
+    Rows { columns, cells }
+}
+
+fn record(node: &Node) -> Vec<(String, &Node)> {
+    let mut record = vec![];
+    match node {
+        Node::Object(_) => flatten("", node, &mut record),
+        other => record.push((String::from("value"), other)),
+    }
+    record
+}
+
+fn flatten<'a>(prefix: &str, node: &'a Node, record: &mut Vec<(String, &'a Node)>) {
+    match node {
+        Node::Object(fields) => {
+            for (key, node) in fields {
+                let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&key, node, record);
+            }
+        }
+        other => record.push((prefix.to_string(), other)),
+    }
+}
+
+fn cell(node: &Node) -> String {
+    match node {
+        Node::Scalar(Value::Null) => String::new(),
+        Node::Scalar(Value::String(s)) => s.clone(),
+        other => serde_json::to_string(other).unwrap(),
+    }
+}
+
+fn table(rows: &Rows) -> String {
+    let widths: Vec<usize> = rows
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            rows.cells
+                .iter()
+                .map(|row| row[i].chars().count())
+                .chain([column.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let line = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![line(&rows.columns)];
+    lines.extend(rows.cells.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn csv(rows: &Rows) -> String {
+    let line = |cells: &[String]| cells.iter().map(|cell| csv_field(cell)).collect::<Vec<_>>().join(",");
+
+    let mut lines = vec![line(&rows.columns)];
+    lines.extend(rows.cells.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct Account {
+        address: &'static str,
+        trading: Trading,
+    }
+
+    #[derive(Serialize)]
+    struct Trading {
+        pending: &'static str,
+        claimed: &'static str,
+    }
+
+    #[test]
+    fn flattens_nested_objects_into_columns_in_field_order() {
+        let value = Account {
+            address: "0xabc",
+            trading: Trading { pending: "1", claimed: "2" },
+        };
+
+        assert_eq!(
+            render(Format::Csv, &value).unwrap(),
+            "address,trading.pending,trading.claimed\n0xabc,1,2"
+        );
+    }
+
+    #[test]
+    fn renders_one_row_per_array_element() {
+        let value = json!([{ "hash": "0x1", "price": "10" }, { "hash": "0x22", "status": "VALID" }]);
+
+        assert_eq!(
+            render(Format::Table, &value).unwrap(),
+            "hash  price  status\n0x1   10\n0x22         VALID"
+        );
+    }
+
+    #[test]
+    fn quotes_csv_fields() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -0,0 +1,384 @@
+//! Synchronous client for scripts and services without an async runtime.
+//!
+//! Mirrors [`crate::api::LooksRareApi`] on top of `reqwest::blocking`, sharing
+//! its request building and response parsing, including the `*_many` bulk
+//! helpers and the [`v2`](LooksRareApi::v2) client. Caching, request
+//! coalescing and rate limiting are only available on the async client.
+
+use crate::api::{
+    address_query,
+    events_query,
+    orders_query,
+    parse_account,
+    parse_collection_information,
+    parse_collection_reward_points,
+    parse_collection_stats,
+    parse_created_order,
+    parse_events,
+    parse_nonce,
+    parse_orders,
+    parse_rewards_epochs,
+    parse_rewards_proof,
+    parse_top_5_listing_rewards_collections,
+    parse_user_rewards,
+    record_outcome,
+    request_span,
+    request_url,
+    CreateOrderRequest,
+    Endpoint,
+    EventsRequest,
+    LooksRareApiError,
+    OrdersRequest,
+    Pagination,
+};
+use crate::constants;
+use crate::types::{
+    Account,
+    CollectionInformation,
+    CollectionRewardPoints,
+    CollectionRewards,
+    CollectionStats,
+    Event,
+    Network,
+    Order,
+    RewardsEpoch,
+    RewardsProof,
+    UserRewards,
+};
+use crate::transport::HttpRequest;
+use ethers_core::types::Address;
+use instant::Instant;
+use reqwest::blocking::Client;
+use std::thread;
+
+pub struct LooksRareApi {
+    client: Client,
+    network: Network,
+}
+
+impl LooksRareApi {
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    pub fn builder() -> LooksRareApiBuilder {
+        LooksRareApiBuilder::default()
+    }
+
+    pub fn get_account(&self, address: Address) -> Result<Account, LooksRareApiError> {
+        self.get(Endpoint::Accounts, &address_query(address)?, |text| parse_account(text, address))
+    }
+
+    pub fn get_orders(&self, req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        self.get(Endpoint::Orders, &orders_query(req)?, parse_orders)
+    }
+
+    /// Every order matching `req`, following the cursor page by page.
+    ///
+    /// Pages hold `req.pagination.first` orders, or as many as the API
+    /// allows when unset; a cursor in `req` is the starting point.
+    pub fn get_all_orders(&self, mut req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        let pagination = req.pagination.take();
+        let first = pagination.as_ref().and_then(|p| p.first).unwrap_or(constants::MAX_ORDERS_PAGE_SIZE);
+        let mut cursor = pagination.and_then(|p| p.cursor);
+
+        let mut orders = vec![];
+        loop {
+            req.pagination = Some(Pagination { first: Some(first), cursor: cursor.take() });
+            let page = self.get_orders(req.clone())?;
+            let last_page = (page.len() as u64) < first;
+
+            cursor = page.last().map(|order| order.hash.clone());
+            orders.extend(page);
+
+            if last_page || cursor.is_none() {
+                return Ok(orders);
+            }
+        }
+    }
+
+    pub fn get_events(&self, req: EventsRequest) -> Result<Vec<Event>, LooksRareApiError> {
+        self.get(Endpoint::Events, &events_query(req)?, parse_events)
+    }
+
+    pub fn create_order(&self, req: CreateOrderRequest) -> Result<Order, LooksRareApiError> {
+        let url = request_url(&format!("{}/orders", self.network.api()), &[])?;
+        send(&self.client, HttpRequest::post_json(url, &req)?, parse_created_order)
+    }
+
+    pub fn get_nonce(&self, address: Address) -> Result<u64, LooksRareApiError> {
+        self.get(Endpoint::OrdersNonce, &address_query(address)?, |text| parse_nonce(text, address))
+    }
+
+    pub fn get_collection_information(&self, address: Address) -> Result<CollectionInformation, LooksRareApiError> {
+        self.get(Endpoint::Collections, &address_query(address)?, |text| parse_collection_information(text, address))
+    }
+
+    pub fn get_collection_stats(&self, address: Address) -> Result<CollectionStats, LooksRareApiError> {
+        self.get(Endpoint::CollectionStats, &address_query(address)?, |text| parse_collection_stats(text, address))
+    }
+
+    pub fn get_top_5_listing_rewards_collections(&self) -> Result<Vec<CollectionRewards>, LooksRareApiError> {
+        self.get(Endpoint::ListingRewards, &[], parse_top_5_listing_rewards_collections)
+    }
+
+    pub fn get_user_rewards(&self, address: Address) -> Result<UserRewards, LooksRareApiError> {
+        self.get(Endpoint::Rewards, &address_query(address)?, |text| parse_user_rewards(text, address))
+    }
+
+    pub fn get_collection_reward_points(&self, address: Address) -> Result<Vec<CollectionRewardPoints>, LooksRareApiError> {
+        self.get(Endpoint::RewardsCollections, &address_query(address)?, |text| parse_collection_reward_points(text, address))
+    }
+
+    pub fn get_rewards_epochs(&self) -> Result<Vec<RewardsEpoch>, LooksRareApiError> {
+        self.get(Endpoint::RewardsEpochs, &[], parse_rewards_epochs)
+    }
+
+    pub fn get_rewards_proof(&self, address: Address) -> Result<RewardsProof, LooksRareApiError> {
+        self.get(Endpoint::RewardsProof, &address_query(address)?, |text| parse_rewards_proof(text, address))
+    }
+
+    /// Client for the v2 API sharing this client's `reqwest` client and network.
+    pub fn v2(&self) -> crate::v2::blocking::LooksRareApi {
+        crate::v2::blocking::LooksRareApi::from_parts(self.client.clone(), self.network.clone())
+    }
+
+    /// Like [`crate::api::LooksRareApi::get_accounts_many`], running each
+    /// group of `concurrency` requests on its own threads.
+    pub fn get_accounts_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<Account, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_account(address))
+    }
+
+    pub fn get_collection_information_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<CollectionInformation, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_collection_information(address))
+    }
+
+    pub fn get_collection_stats_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<CollectionStats, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_collection_stats(address))
+    }
+
+    /// Runs `req` once per token id, overriding its `token_id` filter.
+    pub fn get_orders_many(
+        &self,
+        req: OrdersRequest,
+        token_ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> Vec<(u64, Result<Vec<Order>, LooksRareApiError>)> {
+        run_many(token_ids, concurrency, |token_id| {
+            self.get_orders(OrdersRequest {
+                token_id: Some(token_id),
+                ..req.clone()
+            })
+        })
+    }
+
+    fn get<T>(
+        &self,
+        endpoint: Endpoint,
+        query: &[(&str, serde_json::Value)],
+        parse: impl FnOnce(&str) -> Result<T, LooksRareApiError>,
+    ) -> Result<T, LooksRareApiError> {
+        let url = request_url(&format!("{}{}", self.network.api(), endpoint.path()), query)?;
+        send(&self.client, HttpRequest::get(url), parse)
+    }
+}
+
+impl Default for LooksRareApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Calls `f` for every key, at most `concurrency` at a time, returning the
+/// results in input order.
+fn run_many<K, T, F>(keys: impl IntoIterator<Item = K>, concurrency: usize, f: F) -> Vec<(K, Result<T, LooksRareApiError>)>
+where
+    K: Clone + Send + Sync,
+    T: Send,
+    F: Fn(K) -> Result<T, LooksRareApiError> + Sync,
+{
+    let keys: Vec<K> = keys.into_iter().collect();
+    let f = &f;
+
+    keys.chunks(concurrency.max(1))
+        .flat_map(|chunk| {
+            thread::scope(|scope| {
+                let handles: Vec<_> = chunk.iter().map(|key| scope.spawn(move || f(key.clone()))).collect();
+                chunk
+                    .iter()
+                    .cloned()
+                    .zip(handles.into_iter().map(|handle| handle.join().unwrap()))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect()
+}
+
+/// Sends `request` in a `looksrare_request` span, like the async client,
+/// and parses the response with `parse`.
+pub(crate) fn send<T>(
+    client: &Client,
+    request: HttpRequest,
+    parse: impl FnOnce(&str) -> Result<T, LooksRareApiError>,
+) -> Result<T, LooksRareApiError> {
+    let span = request_span(&request);
+    let _entered = span.enter();
+    let start = Instant::now();
+
+    let mut builder = client.request(request.method, request.url).headers(request.headers);
+    if let Some(body) = request.body {
+        builder = builder.body(body);
+    }
+
+    let mut status = None;
+    let result = builder
+        .send()
+        .and_then(|res| {
+            status = Some(res.status());
+            res.text()
+        })
+        .map_err(LooksRareApiError::from)
+        .and_then(|text| parse(&text));
+
+    record_outcome(&span, start.elapsed(), status, &result);
+    result
+}
+
+pub struct LooksRareApiBuilder {
+    client: Option<Client>,
+    network: Network,
+}
+
+impl Default for LooksRareApiBuilder {
+    fn default() -> Self {
+        Self {
+            client: None,
+            network: Network::Mainnet,
+        }
+    }
+}
+
+impl LooksRareApiBuilder {
+    pub fn network(mut self, network: Network) -> Self {
+        self.network = network;
+        self
+    }
+
+    /// Points the client at a custom API deployment, e.g. a local mock.
+    pub fn base_url(self, base_url: impl Into<String>) -> Self {
+        self.network(Network::Custom(base_url.into()))
+    }
+
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn build(self) -> LooksRareApi {
+        LooksRareApi {
+            client: self.client.unwrap_or_default(),
+            network: self.network,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_get;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    // reqwest's blocking client owns a runtime, which must not be created or
+    // dropped on an async worker thread.
+    async fn blocking<T: Send + 'static>(
+        server: &MockServer,
+        f: impl FnOnce(LooksRareApi) -> T + Send + 'static,
+    ) -> T {
+        let uri = server.uri();
+        tokio::task::spawn_blocking(move || f(LooksRareApi::builder().base_url(uri).build()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn can_get_account() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/accounts", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/accounts").await;
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let account: Account = blocking(&server, move |api| api.get_account(input_address)).await.unwrap();
+        let output_address: Address = account.address;
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn can_get_orders() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[("tokenId", "1"), ("isOrderAsk", "true")], "v1/orders").await;
+        let req = OrdersRequest {
+            is_order_ask: Some(true),
+            collection: None,
+            token_id: Some(1),
+            signer: None,
+            nonce: None,
+            strategy: None,
+            currency: None,
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: None,
+            pagination: None,
+            sort: None,
+        };
+
+        let orders = blocking(&server, move |api| api.get_orders(req)).await.unwrap();
+        assert!(!orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_items_do_not_abort_the_batch() {
+        let server = MockServer::start().await;
+        let found: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let missing: Address = "0x0000000000000000000000000000000000000001".parse().unwrap();
+        mock_get(&server, "/api/v1/collections/stats", &[("address", &format!("{:?}", found))], "v1/collections_stats").await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .and(query_param("address", format!("{:?}", missing)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "message": null, "data": null })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let results = blocking(&server, move |api| api.get_collection_stats_many([missing, found], 2)).await;
+
+        assert_eq!(results[0].0, missing);
+        assert!(matches!(results[0].1, Err(LooksRareApiError::CollectionNotFound { .. })));
+        assert_eq!(results[1].1.as_ref().unwrap().address, found);
+    }
+
+    #[tokio::test]
+    async fn can_get_v2_nonces() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v2/orders/nonce", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v2/orders_nonce").await;
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonces = blocking(&server, move |api| api.v2().get_nonces(input_address)).await.unwrap();
+
+        assert_eq!(nonces.bid_nonce, ethers_core::types::U256::from(5));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::api::{LooksRareApi, LooksRareApiError, OrdersRequest};
+use crate::types::{Account, CollectionInformation, CollectionStats, Order};
+use ethers_core::types::Address;
+use futures::stream::{self, StreamExt};
+use std::future::Future;
+
+/// Batch helpers that run at most `concurrency` requests at a time.
+///
+/// Requests still go through the client's cache, coalescing and rate
+/// limiter. Results come back in input order, each paired with its input,
+/// so one failing item does not abort the rest of the batch.
+impl LooksRareApi {
+    pub async fn get_accounts_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<Account, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_account(address)).await
+    }
+
+    pub async fn get_collection_information_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<CollectionInformation, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_collection_information(address)).await
+    }
+
+    pub async fn get_collection_stats_many(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        concurrency: usize,
+    ) -> Vec<(Address, Result<CollectionStats, LooksRareApiError>)> {
+        run_many(addresses, concurrency, |address| self.get_collection_stats(address)).await
+    }
+
+    /// Runs `req` once per token id, overriding its `token_id` filter.
+    pub async fn get_orders_many(
+        &self,
+        req: OrdersRequest,
+        token_ids: impl IntoIterator<Item = u64>,
+        concurrency: usize,
+    ) -> Vec<(u64, Result<Vec<Order>, LooksRareApiError>)> {
+        run_many(token_ids, concurrency, |token_id| {
+            self.get_orders(OrdersRequest {
+                token_id: Some(token_id),
+                ..req.clone()
+            })
+        })
+        .await
+    }
+}
+
+async fn run_many<K, T, F, Fut>(
+    keys: impl IntoIterator<Item = K>,
+    concurrency: usize,
+    f: F,
+) -> Vec<(K, Result<T, LooksRareApiError>)>
+where
+    K: Clone,
+    F: Fn(K) -> Fut,
+    Fut: Future<Output = Result<T, LooksRareApiError>>,
+{
+    stream::iter(keys)
+        .map(|key| {
+            let request = f(key.clone());
+            async move { (key, request.await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, fixture};
+    use std::time::Duration;
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[tokio::test]
+    async fn failed_items_do_not_abort_the_batch() {
+        let server = MockServer::start().await;
+        let found: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let missing: Address = "0x0000000000000000000000000000000000000001".parse().unwrap();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .and(query_param("address", format!("{:?}", found)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture("v1/collections_stats")))
+            .expect(1..=2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .and(query_param("address", format!("{:?}", missing)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "message": null, "data": null })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let results = api(&server).get_collection_stats_many([found, missing, found], 2).await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, found);
+        assert_eq!(results[0].1.as_ref().unwrap().address, found);
+        assert!(matches!(results[1].1, Err(LooksRareApiError::CollectionNotFound { .. })));
+        assert!(results[2].1.is_ok());
+    }
+
+    #[tokio::test]
+    async fn bounds_concurrency() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(fixture("v1/collections_stats"))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .expect(4)
+            .mount(&server)
+            .await;
+        let addresses = (1..=4u64).map(Address::from_low_u64_be);
+
+        let start = std::time::Instant::now();
+        let results = api(&server).get_collection_stats_many(addresses, 2).await;
+
+        assert!(results.iter().all(|(_, r)| r.is_ok()));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::api::Endpoint;
+use instant::Instant;
+use lru::LruCache;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Storage backend for cached API responses.
+///
+/// Implementations must drop or ignore entries whose TTL has elapsed.
+pub trait CacheStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<String>;
+
+    fn insert(&self, key: String, value: String, ttl: Duration);
+
+    fn clear(&self);
+}
+
+/// In-memory least-recently-used store.
+pub struct LruStore {
+    entries: Mutex<LruCache<String, (Instant, String)>>,
+}
+
+impl LruStore {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl Default for LruStore {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(1024).unwrap())
+    }
+}
+
+impl CacheStore for LruStore {
+    fn get(&self, key: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((expires_at, value)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: String, value: String, ttl: Duration) {
+        self.entries.lock().unwrap().put(key, (Instant::now() + ttl, value));
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// Response cache for [`LooksRareApi`](crate::api::LooksRareApi).
+///
+/// Each endpoint has its own TTL; endpoints without one are never cached.
+/// By default collection information is kept for an hour, collection stats
+/// for 30 seconds, and orders, nonces and user rewards are always fetched.
+pub struct Cache {
+    store: Box<dyn CacheStore>,
+    ttls: HashMap<Endpoint, Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self::with_store(LruStore::default())
+    }
+
+    pub fn with_store(store: impl CacheStore + 'static) -> Self {
+        let ttls = HashMap::from([
+            (Endpoint::Accounts, Duration::from_secs(5 * 60)),
+            (Endpoint::Collections, Duration::from_secs(60 * 60)),
+            (Endpoint::CollectionStats, Duration::from_secs(30)),
+            (Endpoint::ListingRewards, Duration::from_secs(5 * 60)),
+            (Endpoint::RewardsEpochs, Duration::from_secs(60 * 60)),
+        ]);
+
+        Self {
+            store: Box::new(store),
+            ttls,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn ttl(mut self, endpoint: Endpoint, ttl: Duration) -> Self {
+        self.ttls.insert(endpoint, ttl);
+        self
+    }
+
+    /// Stops caching `endpoint`.
+    pub fn disable(mut self, endpoint: Endpoint) -> Self {
+        self.ttls.remove(&endpoint);
+        self
+    }
+
+    pub fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.store.clear();
+    }
+
+    pub(crate) fn get(&self, endpoint: Endpoint, key: &str) -> Option<String> {
+        self.ttls.get(&endpoint)?;
+
+        let value = self.store.get(key);
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        value
+    }
+
+    pub(crate) fn insert(&self, endpoint: Endpoint, key: String, value: String) {
+        if let Some(ttl) = self.ttls.get(&endpoint) {
+            self.store.insert(key, value, *ttl);
+        }
+    }
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hits and misses across cached endpoints.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_store_expires_entries() {
+        let store = LruStore::default();
+        store.insert(String::from("fresh"), String::from("a"), Duration::from_secs(60));
+        store.insert(String::from("stale"), String::from("b"), Duration::ZERO);
+
+        assert_eq!(store.get("fresh").as_deref(), Some("a"));
+        assert_eq!(store.get("stale"), None);
+    }
+
+    #[test]
+    fn lru_store_evicts_least_recently_used() {
+        let store = LruStore::new(NonZeroUsize::new(2).unwrap());
+        let ttl = Duration::from_secs(60);
+        store.insert(String::from("a"), String::from("1"), ttl);
+        store.insert(String::from("b"), String::from("2"), ttl);
+        store.get("a");
+        store.insert(String::from("c"), String::from("3"), ttl);
+
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+
+    #[test]
+    fn uncached_endpoints_skip_the_store() {
+        let cache = Cache::new();
+        cache.insert(Endpoint::Orders, String::from("orders"), String::from("[]"));
+
+        assert_eq!(cache.get(Endpoint::Orders, "orders"), None);
+        assert_eq!(cache.metrics(), CacheMetrics::default());
+    }
+}
@@ -1,4 +1,29 @@
 pub const VERSION: u64 = 1;
 pub const API_PATH: &str = "/api/v";
 
-pub const API_BASE_MAINNET: &str = "https://api.looksrare.org";
\ No newline at end of file
+pub const API_BASE_MAINNET: &str = "https://api.looksrare.org";
+
+/// Largest `pagination[first]` the orders endpoint accepts.
+pub const MAX_ORDERS_PAGE_SIZE: u64 = 150;
+
+/// Largest `pagination[first]` the events endpoint accepts.
+pub const MAX_EVENTS_PAGE_SIZE: u64 = 150;
+
+pub const TRADING_REWARDS_DISTRIBUTOR_MAINNET: &str = "0x453c1208B400fE47aCF275315F14E8F9F9fbC3cD";
+
+pub const LOOKS_TOKEN_MAINNET: &str = "0xf4d2888d29D722226FafA5d9B24F9164c092421E";
+pub const FEE_SHARING_SYSTEM_MAINNET: &str = "0xBcD7254A1D759EFA08eC7c3291B2E85c5dCC12ce";
+pub const AGGREGATOR_FEE_SHARING_MAINNET: &str = "0x3ab16Af1315dc6C95F83Cbf522fecF98D00fd9ba";
+
+pub const LOOKSRARE_EXCHANGE_MAINNET: &str = "0x59728544B08AB483533076417FbBB2fD0B17CE3a";
+pub const STRATEGY_STANDARD_SALE_MAINNET: &str = "0x579af6FD30BF83a5Ac0D636bc619f98DBdeB930c";
+pub const STRATEGY_COLLECTION_SALE_MAINNET: &str = "0x86F909F70813CdB1Bc733f4D97Dc6b03B8e7E8F3";
+pub const WETH_MAINNET: &str = "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2";
+pub const ROYALTY_FEE_MANAGER_MAINNET: &str = "0x7358182024c9f1B2e6b0153e60bf6156B7eF4906";
+pub const TRANSFER_MANAGER_ERC721_MAINNET: &str = "0xf42aa99F011A1fA7CDA90E5E98b277E306BcA83e";
+pub const TRANSFER_MANAGER_ERC1155_MAINNET: &str = "0xFED24eC7E22f573c2e08AEF55aA6797Ca2b3A051";
+
+pub const VERSION_V2: u64 = 2;
+
+pub const LOOKSRARE_PROTOCOL_MAINNET: &str = "0x0000000000E655fAe4d56241588680F86E3b2377";
+pub const TRANSFER_MANAGER_V2_MAINNET: &str = "0x000000000060C4Ca14CfC4325359062ace33Fe3D";
@@ -0,0 +1,122 @@
+use ethers::prelude::abigen;
+
+abigen!(
+    TradingRewardsDistributor,
+    r#"[
+        function claim(uint256 amount, bytes32[] calldata merkleProof) external
+        function canClaim(address user, uint256 amount, bytes32[] calldata merkleProof) external view returns (bool, uint256)
+        function currentRewardRound() external view returns (uint256)
+        function merkleRootOfRewardRound(uint256 round) external view returns (bytes32)
+        function amountClaimedByUser(address user) external view returns (uint256)
+        function hasUserClaimedForRewardRound(uint256 round, address user) external view returns (bool)
+        function isPaused() external view returns (bool)
+        event RewardsClaim(address indexed user, uint256 indexed rewardRound, uint256 amount)
+    ]"#,
+);
+
+abigen!(
+    LooksRareToken,
+    r#"[
+        function name() external view returns (string)
+        function symbol() external view returns (string)
+        function decimals() external view returns (uint8)
+        function totalSupply() external view returns (uint256)
+        function SUPPLY_CAP() external view returns (uint256)
+        function balanceOf(address account) external view returns (uint256)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function transfer(address recipient, uint256 amount) external returns (bool)
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool)
+        event Transfer(address indexed from, address indexed to, uint256 value)
+        event Approval(address indexed owner, address indexed spender, uint256 value)
+    ]"#,
+);
+
+abigen!(
+    FeeSharingSystem,
+    r#"[
+        function deposit(uint256 amount, bool claimRewardToken) external
+        function harvest() external
+        function withdraw(uint256 shares, bool claimRewardToken) external
+        function withdrawAll(bool claimRewardToken) external
+        function calculatePendingRewards(address user) external view returns (uint256)
+        function calculateSharePriceInLOOKS() external view returns (uint256)
+        function calculateSharesValueInLOOKS(address user) external view returns (uint256)
+        function userInfo(address user) external view returns (uint256 shares, uint256 userRewardPerTokenPaid, uint256 rewards)
+        function totalShares() external view returns (uint256)
+        function currentRewardPerBlock() external view returns (uint256)
+        function periodEndBlock() external view returns (uint256)
+        function rewardToken() external view returns (address)
+        function looksRareToken() external view returns (address)
+        event Deposit(address indexed user, uint256 amount, uint256 harvestedAmount)
+        event Harvest(address indexed user, uint256 harvestedAmount)
+        event Withdraw(address indexed user, uint256 amount, uint256 harvestedAmount)
+    ]"#,
+);
+
+abigen!(
+    AggregatorFeeSharingWithUniswapV3,
+    r#"[
+        function deposit(uint256 amount) external
+        function withdraw(uint256 shares) external
+        function withdrawAll() external
+        function harvestAndSellAndCompound() external
+        function calculateSharePriceInLOOKS() external view returns (uint256)
+        function calculateSharesValueInLOOKS(address user) external view returns (uint256)
+        function userInfo(address user) external view returns (uint256)
+        function totalShares() external view returns (uint256)
+        event Deposit(address indexed user, uint256 amount)
+        event Withdraw(address indexed user, uint256 amount)
+    ]"#,
+);
+
+abigen!(
+    LooksRareExchange,
+    r#"[
+        struct MakerOrder { bool isOrderAsk; address signer; address collection; uint256 price; uint256 tokenId; uint256 amount; address strategy; address currency; uint256 nonce; uint256 startTime; uint256 endTime; uint256 minPercentageToAsk; bytes params; uint8 v; bytes32 r; bytes32 s; }
+        struct TakerOrder { bool isOrderAsk; address taker; uint256 price; uint256 tokenId; uint256 minPercentageToAsk; bytes params; }
+        function matchAskWithTakerBid(TakerOrder takerBid, MakerOrder makerAsk) external
+        function matchAskWithTakerBidUsingETHAndWETH(TakerOrder takerBid, MakerOrder makerAsk) external payable
+        function matchBidWithTakerAsk(TakerOrder takerAsk, MakerOrder makerBid) external
+        function cancelAllOrdersForSender(uint256 minNonce) external
+        function cancelMultipleMakerOrders(uint256[] calldata orderNonces) external
+        function userMinOrderNonce(address user) external view returns (uint256)
+        function isUserOrderNonceExecutedOrCancelled(address user, uint256 orderNonce) external view returns (bool)
+        event CancelAllOrders(address indexed user, uint256 newMinNonce)
+        event CancelMultipleOrders(address indexed user, uint256[] orderNonces)
+        event RoyaltyPayment(address indexed collection, uint256 indexed tokenId, address indexed royaltyRecipient, address currency, uint256 amount)
+        event TakerAsk(bytes32 orderHash, uint256 orderNonce, address indexed taker, address indexed maker, address indexed strategy, address currency, address collection, uint256 tokenId, uint256 amount, uint256 price)
+        event TakerBid(bytes32 orderHash, uint256 orderNonce, address indexed taker, address indexed maker, address indexed strategy, address currency, address collection, uint256 tokenId, uint256 amount, uint256 price)
+    ]"#,
+);
+
+abigen!(
+    ExecutionStrategy,
+    r#"[
+        function viewProtocolFee() external view returns (uint256)
+    ]"#,
+);
+
+abigen!(
+    RoyaltyFeeManager,
+    r#"[
+        function calculateRoyaltyFeeAndGetRecipient(address collection, uint256 tokenId, uint256 amount) external view returns (address, uint256)
+    ]"#,
+);
+
+abigen!(
+    ERC721,
+    r#"[
+        function ownerOf(uint256 tokenId) external view returns (address)
+        function isApprovedForAll(address owner, address operator) external view returns (bool)
+        function supportsInterface(bytes4 interfaceId) external view returns (bool)
+    ]"#,
+);
+
+abigen!(
+    ERC1155,
+    r#"[
+        function balanceOf(address account, uint256 id) external view returns (uint256)
+        function isApprovedForAll(address account, address operator) external view returns (bool)
+    ]"#,
+);
@@ -0,0 +1,376 @@
+use crate::api::{EventsRequest, LooksRareApi, LooksRareApiError, OrdersRequest, Pagination, Sort, Status};
+use crate::constants;
+use crate::types::{EventType, Order};
+use ethers_core::types::{Address, U256};
+use futures::channel::mpsc::UnboundedSender;
+use futures_timer::Delay;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Hashes remembered for deduplication.
+const SEEN_CAPACITY: usize = 10_000;
+
+/// Which new listings a [`ListingFeed`] emits.
+///
+/// Filters compose with [`and`](Self::and), [`or`](Self::or) and
+/// [`not`](Self::not). Prices are per item, in wei of the order currency.
+#[derive(Clone)]
+pub enum Filter {
+    /// Priced below `bps` basis points of the collection floor, e.g. 8000
+    /// for under 80% of floor. Never matches when the floor is unknown.
+    BelowFloor { bps: u64 },
+    MaxPrice(U256),
+    TokenIds(HashSet<U256>),
+    Currencies(HashSet<Address>),
+    /// Tokens ranked `max_rank` or better in `ranks`, a token id to rarity
+    /// rank table from any rarity source; the API has no trait data.
+    Rarity { ranks: Arc<HashMap<U256, u64>>, max_rank: u64 },
+    Custom(Arc<dyn Fn(&Order) -> bool + Send + Sync>),
+    All(Vec<Filter>),
+    Any(Vec<Filter>),
+    Not(Box<Filter>),
+}
+
+impl Filter {
+    pub fn and(self, other: Filter) -> Filter {
+        match self {
+            Filter::All(mut filters) => {
+                filters.push(other);
+                Filter::All(filters)
+            }
+            filter => Filter::All(vec![filter, other]),
+        }
+    }
+
+    pub fn or(self, other: Filter) -> Filter {
+        match self {
+            Filter::Any(mut filters) => {
+                filters.push(other);
+                Filter::Any(filters)
+            }
+            filter => Filter::Any(vec![filter, other]),
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+
+    /// Whether `order` passes, `floor` being the floor of its collection.
+    pub fn matches(&self, order: &Order, floor: Option<U256>) -> bool {
+        match self {
+            Filter::BelowFloor { bps } => match (order.unit_price(), floor) {
+                (Some(price), Some(floor)) => price * U256::from(10_000) < floor * U256::from(*bps),
+                _ => false,
+            },
+            Filter::MaxPrice(max) => matches!(order.unit_price(), Some(price) if price <= *max),
+            Filter::TokenIds(ids) => matches!(token_id(order), Some(id) if ids.contains(&id)),
+            Filter::Currencies(currencies) => currencies.contains(&order.currency_address),
+            Filter::Rarity { ranks, max_rank } => {
+                matches!(token_id(order).and_then(|id| ranks.get(&id)), Some(rank) if rank <= max_rank)
+            }
+            Filter::Custom(f) => f(order),
+            Filter::All(filters) => filters.iter().all(|filter| filter.matches(order, floor)),
+            Filter::Any(filters) => filters.iter().any(|filter| filter.matches(order, floor)),
+            Filter::Not(filter) => !filter.matches(order, floor),
+        }
+    }
+
+    fn needs_floor(&self) -> bool {
+        match self {
+            Filter::BelowFloor { .. } => true,
+            Filter::All(filters) | Filter::Any(filters) => filters.iter().any(Filter::needs_floor),
+            Filter::Not(filter) => filter.needs_floor(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::BelowFloor { bps } => f.debug_struct("BelowFloor").field("bps", bps).finish(),
+            Filter::MaxPrice(max) => f.debug_tuple("MaxPrice").field(max).finish(),
+            Filter::TokenIds(ids) => f.debug_tuple("TokenIds").field(ids).finish(),
+            Filter::Currencies(currencies) => f.debug_tuple("Currencies").field(currencies).finish(),
+            Filter::Rarity { ranks, max_rank } => f
+                .debug_struct("Rarity")
+                .field("ranks", &ranks.len())
+                .field("max_rank", max_rank)
+                .finish(),
+            Filter::Custom(_) => f.write_str("Custom"),
+            Filter::All(filters) => f.debug_tuple("All").field(filters).finish(),
+            Filter::Any(filters) => f.debug_tuple("Any").field(filters).finish(),
+            Filter::Not(filter) => f.debug_tuple("Not").field(filter).finish(),
+        }
+    }
+}
+
+/// New listings matching a [`Filter`], polled from the API.
+///
+/// Each poll reads the newest valid asks and, unless disabled, the latest
+/// `LIST` events, so listings are seen even when a busy order list pages
+/// past them. Orders are emitted once, by hash. The first poll only
+/// records what is already listed. Floors come from the collection stats.
+pub struct ListingFeed {
+    api: Arc<LooksRareApi>,
+    collection: Option<Address>,
+    filter: Option<Filter>,
+    events: bool,
+    interval: Duration,
+    last_event: Option<u64>,
+    seen: HashSet<String>,
+    seen_order: VecDeque<String>,
+    started: bool,
+}
+
+impl ListingFeed {
+    pub fn new(api: Arc<LooksRareApi>) -> Self {
+        Self {
+            api,
+            collection: None,
+            filter: None,
+            events: true,
+            interval: Duration::from_secs(5),
+            last_event: None,
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            started: false,
+        }
+    }
+
+    /// Only listings of `collection`. Defaults to every collection.
+    pub fn collection(mut self, collection: Address) -> Self {
+        self.collection = Some(collection);
+        self
+    }
+
+    /// Emitted listings must match `filter`. Defaults to every listing.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Whether to also poll the events endpoint. Defaults to `true`.
+    pub fn events(mut self, events: bool) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Time between polls in [`run`](Self::run). Defaults to 5 seconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Fetches new listings and returns those matching the filter, oldest
+    /// first.
+    pub async fn poll(&mut self) -> Result<Vec<Order>, LooksRareApiError> {
+        let mut listings = self.newest_orders().await?;
+        if self.events {
+            match self.listed_since().await {
+                Ok(listed) => listings.extend(listed),
+                Err(e) => tracing::warn!(error = %e, "listing events unavailable"),
+            }
+        }
+
+        let fresh: Vec<Order> = listings.into_iter().filter(|order| self.remember(&order.hash)).collect();
+        if !self.started {
+            self.started = true;
+            return Ok(vec![]);
+        }
+
+        let filter = match &self.filter {
+            Some(filter) => filter,
+            None => return Ok(fresh),
+        };
+
+        let mut floors = HashMap::new();
+        let mut matched = vec![];
+        for order in fresh {
+            let floor = if filter.needs_floor() {
+                self.floor(order.collection_address, &mut floors).await
+            } else {
+                None
+            };
+            if filter.matches(&order, floor) {
+                matched.push(order);
+            }
+        }
+        Ok(matched)
+    }
+
+    /// Polls every [`interval`](Self::interval) and sends matching listings
+    /// to `listings` until it is closed. Returns the first API error.
+    pub async fn run(&mut self, listings: UnboundedSender<Order>) -> Result<(), LooksRareApiError> {
+        while !listings.is_closed() {
+            for order in self.poll().await? {
+                let _ = listings.unbounded_send(order);
+            }
+            Delay::new(self.interval).await;
+        }
+        Ok(())
+    }
+
+    /// The newest valid asks, oldest first.
+    async fn newest_orders(&self) -> Result<Vec<Order>, LooksRareApiError> {
+        let mut orders = self
+            .api
+            .get_orders(OrdersRequest {
+                is_order_ask: Some(true),
+                collection: self.collection,
+                token_id: None,
+                signer: None,
+                nonce: None,
+                strategy: None,
+                currency: None,
+                price: None,
+                start_time: None,
+                end_time: None,
+                status: Some(vec![Status::Valid]),
+                pagination: Some(Pagination { first: Some(constants::MAX_ORDERS_PAGE_SIZE), cursor: None }),
+                sort: Some(Sort::Newest),
+            })
+            .await?;
+        orders.reverse();
+        Ok(orders)
+    }
+
+    /// Still valid orders of the `LIST` events since the last poll, oldest
+    /// first.
+    async fn listed_since(&mut self) -> Result<Vec<Order>, LooksRareApiError> {
+        let events = self
+            .api
+            .get_events(EventsRequest {
+                collection: self.collection,
+                type_: Some(EventType::List),
+                pagination: Some(Pagination { first: Some(constants::MAX_EVENTS_PAGE_SIZE), cursor: None }),
+                ..Default::default()
+            })
+            .await?;
+
+        let last_event = self.last_event.unwrap_or_default();
+        self.last_event = events.iter().map(|event| event.id).max().max(self.last_event);
+
+        Ok(events
+            .into_iter()
+            .rev()
+            .filter(|event| event.id > last_event && event.type_ == EventType::List)
+            .filter_map(|event| event.order)
+            .filter(|order| order.is_order_ask && order.status == Status::Valid.to_str())
+            .collect())
+    }
+
+    async fn floor(&self, collection: Address, floors: &mut HashMap<Address, Option<U256>>) -> Option<U256> {
+        if let Some(floor) = floors.get(&collection) {
+            return *floor;
+        }
+
+        let floor = match self.api.get_collection_stats(collection).await {
+            Ok(stats) => U256::from_dec_str(&stats.floor_price).ok(),
+            Err(e) => {
+                tracing::warn!(?collection, error = %e, "collection floor unavailable");
+                None
+            }
+        };
+        floors.insert(collection, floor);
+        floor
+    }
+
+    /// Records `hash`, returning whether it is new.
+    fn remember(&mut self, hash: &str) -> bool {
+        if !self.seen.insert(hash.to_string()) {
+            return false;
+        }
+
+        self.seen_order.push_back(hash.to_string());
+        if self.seen_order.len() > SEEN_CAPACITY {
+            if let Some(oldest) = self.seen_order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+fn token_id(order: &Order) -> Option<U256> {
+    U256::from_dec_str(&order.token_id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, fixture, mock_get};
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    fn listing(token_id: &str, price: U256, currency: Address) -> Order {
+        let mut order: Order = serde_json::from_value(fixture("v1/orders")["data"][0].clone()).unwrap();
+        order.token_id = token_id.to_string();
+        order.price = price.to_string();
+        order.currency_address = currency;
+        order
+    }
+
+    #[test]
+    fn filters_compose() {
+        let weth: Address = constants::WETH_MAINNET.parse().unwrap();
+        let floor = Some(U256::exp10(18));
+        let cheap = listing("1", U256::exp10(17) * 7, weth);
+        let rare = listing("2", U256::exp10(18) * 2, Address::zero());
+
+        let below_floor = Filter::BelowFloor { bps: 8_000 };
+        assert!(below_floor.matches(&cheap, floor));
+        assert!(!below_floor.matches(&cheap, None));
+        assert!(!below_floor.matches(&rare, floor));
+
+        let ranks = Arc::new(HashMap::from([(U256::from(1), 900), (U256::from(2), 3)]));
+        let filter = below_floor
+            .and(Filter::Currencies(HashSet::from([weth])))
+            .or(Filter::Rarity { ranks, max_rank: 10 }.and(Filter::MaxPrice(U256::exp10(18) * 3)));
+        assert!(filter.matches(&cheap, floor));
+        assert!(filter.matches(&rare, floor));
+        assert!(!filter.clone().not().matches(&rare, floor));
+        assert!(!Filter::TokenIds(HashSet::from([U256::from(3)])).matches(&rare, floor));
+    }
+
+    #[tokio::test]
+    async fn emits_new_matching_listings_once() {
+        let server = MockServer::start().await;
+        let page = |name: &str| ResponseTemplate::new(200).set_body_json(fixture(name));
+        for (fixture, times) in [("v1/orders", 1), ("v1/orders_page", 2)] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/orders"))
+                .and(query_param("sort", "NEWEST"))
+                .respond_with(page(fixture))
+                .up_to_n_times(times)
+                .mount(&server)
+                .await;
+        }
+        for (fixture, times) in [("v1/events_latest", 1), ("v1/events", 2)] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/events"))
+                .and(query_param("type", "LIST"))
+                .respond_with(page(fixture))
+                .up_to_n_times(times)
+                .mount(&server)
+                .await;
+        }
+        let collection: Address = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap();
+        mock_get(&server, "/api/v1/collections/stats", &[("address", &format!("{:?}", collection))], "v1/collections_stats").await;
+
+        let token_ids = HashSet::from([U256::from(1003), U256::from(1005)]);
+        let mut feed = ListingFeed::new(Arc::new(api(&server)))
+            .filter(Filter::BelowFloor { bps: 9_000 }.or(Filter::TokenIds(token_ids)));
+
+        assert!(feed.poll().await.unwrap().is_empty());
+        let matched: Vec<String> = feed.poll().await.unwrap().into_iter().map(|order| order.token_id).collect();
+        assert_eq!(matched, ["1000", "1005"]);
+        assert!(feed.poll().await.unwrap().is_empty());
+    }
+}
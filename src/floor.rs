@@ -0,0 +1,489 @@
+use crate::api::{LooksRareApi, LooksRareApiError, OrdersRequest, Pagination, Sort, Status};
+use crate::constants;
+use crate::transport::{HttpRequest, ReqwestTransport, Transport, TransportError};
+use crate::types::{u256_dec, Order};
+use async_trait::async_trait;
+use ethers_core::types::{Address, U256};
+use futures::channel::mpsc::UnboundedSender;
+use futures::future::join_all;
+use futures_timer::Delay;
+use reqwest::Url;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Emitted by a [`FloorWatcher`]. Prices are per item, in wei.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FloorEvent {
+    /// The floor moved by at least the watcher's `change_bps` since the last
+    /// `Moved` event, or since the first check.
+    Moved {
+        collection: Address,
+        #[serde(with = "u256_dec")]
+        from: U256,
+        #[serde(with = "u256_dec")]
+        to: U256,
+        change_bps: i64,
+    },
+    /// The floor went from one side of a watched threshold to the other.
+    Crossed {
+        collection: Address,
+        #[serde(with = "u256_dec")]
+        threshold: U256,
+        #[serde(with = "u256_dec")]
+        price: U256,
+        direction: Direction,
+    },
+    /// The lowest valid ask and `CollectionStats.floor_price` differ by at
+    /// least `change_bps`, e.g. because the stats lag behind. Reported again
+    /// only once either side moves by `change_bps` from the last report, or
+    /// after the two have agreed in between.
+    Diverged {
+        collection: Address,
+        #[serde(with = "u256_dec")]
+        lowest_ask: U256,
+        #[serde(with = "u256_dec")]
+        stats_floor: U256,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+pub type SinkError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Where a [`FloorWatcher`] delivers its events.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait FloorSink: Send + Sync {
+    async fn send(&self, event: &FloorEvent) -> Result<(), SinkError>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FloorSink for UnboundedSender<FloorEvent> {
+    async fn send(&self, event: &FloorEvent) -> Result<(), SinkError> {
+        Ok(self.unbounded_send(event.clone())?)
+    }
+}
+
+/// Calls a function with every event.
+pub struct Callback<F>(pub F);
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<F: Fn(&FloorEvent) + Send + Sync> FloorSink for Callback<F> {
+    async fn send(&self, event: &FloorEvent) -> Result<(), SinkError> {
+        (self.0)(event);
+        Ok(())
+    }
+}
+
+/// POSTs every event as JSON to a URL.
+pub struct Webhook {
+    url: Url,
+    transport: Arc<dyn Transport>,
+}
+
+impl Webhook {
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            transport: Arc::new(ReqwestTransport::default()),
+        }
+    }
+
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl FloorSink for Webhook {
+    async fn send(&self, event: &FloorEvent) -> Result<(), SinkError> {
+        let res = self.transport.send(HttpRequest::post_json(self.url.clone(), event)?).await?;
+        if !res.status.is_success() {
+            return Err(TransportError::from(format!("webhook responded with {}", res.status)));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Watch {
+    thresholds: Vec<U256>,
+    /// Floor of the last `Moved` event, or of the first check.
+    reported: Option<U256>,
+    last: Option<U256>,
+    /// Lowest ask and stats floor of the last `Diverged` event, until they
+    /// agree again.
+    diverged: Option<(U256, U256)>,
+}
+
+/// Periodically checks the floors of a set of collections.
+///
+/// The floor is the lowest price per item among the first page of valid
+/// asks, fetched with [`Sort::PriceAsc`]; the API sorts by total price, so
+/// a cheaper multi-item listing further down is not seen. Collections
+/// without listings are skipped until one appears. Failed checks and sink
+/// errors are logged and do not stop the other collections or sinks.
+pub struct FloorWatcher {
+    api: Arc<LooksRareApi>,
+    watches: HashMap<Address, Watch>,
+    change_bps: u64,
+    interval: Duration,
+    sinks: Vec<Box<dyn FloorSink>>,
+}
+
+impl FloorWatcher {
+    pub fn new(api: Arc<LooksRareApi>) -> Self {
+        Self {
+            api,
+            watches: HashMap::new(),
+            change_bps: 500,
+            interval: Duration::from_secs(60),
+            sinks: vec![],
+        }
+    }
+
+    /// Watches `collection`, reporting whenever its floor crosses one of
+    /// `thresholds`.
+    pub fn watch(mut self, collection: Address, thresholds: impl IntoIterator<Item = U256>) -> Self {
+        self.watches.entry(collection).or_default().thresholds.extend(thresholds);
+        self
+    }
+
+    /// Smallest floor move, and divergence from the collection stats, worth
+    /// reporting, in basis points. Defaults to 500 (5%).
+    pub fn change_bps(mut self, change_bps: u64) -> Self {
+        self.change_bps = change_bps;
+        self
+    }
+
+    /// Time between checks in [`run`](Self::run). Defaults to a minute.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn sink(mut self, sink: impl FloorSink + 'static) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Checks every collection once and delivers the resulting events.
+    pub async fn check(&mut self) -> Vec<FloorEvent> {
+        let api = &self.api;
+        let floors = join_all(self.watches.keys().map(|&collection| async move {
+            (collection, floor(api, collection).await)
+        }))
+        .await;
+
+        let mut events = vec![];
+        for (collection, floor) in floors {
+            match floor {
+                Ok((Some(lowest_ask), stats_floor)) => {
+                    events.extend(self.evaluate(collection, lowest_ask, stats_floor));
+                }
+                Ok((None, _)) => {}
+                Err(e) => tracing::warn!(?collection, error = %e, "floor check failed"),
+            }
+        }
+
+        for event in &events {
+            for sink in &self.sinks {
+                if let Err(e) = sink.send(event).await {
+                    tracing::warn!(error = %e, "floor sink failed");
+                }
+            }
+        }
+        events
+    }
+
+    pub async fn run(&mut self) {
+        loop {
+            self.check().await;
+            Delay::new(self.interval).await;
+        }
+    }
+
+    fn evaluate(&mut self, collection: Address, lowest_ask: U256, stats_floor: Option<U256>) -> Vec<FloorEvent> {
+        let change_bps = self.change_bps;
+        let watch = self.watches.entry(collection).or_default();
+        let mut events = vec![];
+
+        match watch.reported {
+            Some(from) => {
+                let change = change_in_bps(from, lowest_ask);
+                if change.unsigned_abs() >= change_bps && from != lowest_ask {
+                    events.push(FloorEvent::Moved {
+                        collection,
+                        from,
+                        to: lowest_ask,
+                        change_bps: change,
+                    });
+                    watch.reported = Some(lowest_ask);
+                }
+            }
+            None => watch.reported = Some(lowest_ask),
+        }
+
+        if let Some(last) = watch.last {
+            for &threshold in &watch.thresholds {
+                let direction = if last < threshold && lowest_ask >= threshold {
+                    Direction::Up
+                } else if last >= threshold && lowest_ask < threshold {
+                    Direction::Down
+                } else {
+                    continue;
+                };
+                events.push(FloorEvent::Crossed {
+                    collection,
+                    threshold,
+                    price: lowest_ask,
+                    direction,
+                });
+            }
+        }
+        watch.last = Some(lowest_ask);
+
+        if let Some(stats_floor) = stats_floor {
+            let moved = |from: U256, to: U256| change_in_bps(from, to).unsigned_abs() >= change_bps && from != to;
+            if moved(lowest_ask, stats_floor) {
+                let reported = matches!(
+                    watch.diverged,
+                    Some((ask, floor)) if !moved(ask, lowest_ask) && !moved(floor, stats_floor)
+                );
+                if !reported {
+                    events.push(FloorEvent::Diverged {
+                        collection,
+                        lowest_ask,
+                        stats_floor,
+                    });
+                    watch.diverged = Some((lowest_ask, stats_floor));
+                }
+            } else {
+                watch.diverged = None;
+            }
+        }
+
+        events
+    }
+}
+
+/// The lowest price per item among the cheapest page of valid asks, and the
+/// floor the collection stats report, if they could be fetched.
+async fn floor(api: &LooksRareApi, collection: Address) -> Result<(Option<U256>, Option<U256>), LooksRareApiError> {
+    let asks = api.get_orders(OrdersRequest {
+        is_order_ask: Some(true),
+        collection: Some(collection),
+        token_id: None,
+        signer: None,
+        nonce: None,
+        strategy: None,
+        currency: None,
+        price: None,
+        start_time: None,
+        end_time: None,
+        status: Some(vec![Status::Valid]),
+        pagination: Some(Pagination {
+            first: Some(constants::MAX_ORDERS_PAGE_SIZE),
+            cursor: None,
+        }),
+        sort: Some(Sort::PriceAsc),
+    });
+    let (asks, stats) = futures::join!(asks, api.get_collection_stats(collection));
+
+    let lowest_ask = asks?.iter().filter_map(Order::unit_price).min();
+    // Moves and crossings only need the asks, so missing stats just skip the
+    // divergence check.
+    let stats_floor = match stats {
+        Ok(stats) => U256::from_dec_str(&stats.floor_price).ok(),
+        Err(e) => {
+            tracing::warn!(?collection, error = %e, "collection stats unavailable");
+            None
+        }
+    };
+
+    Ok((lowest_ask, stats_floor))
+}
+
+fn change_in_bps(from: U256, to: U256) -> i64 {
+    if from.is_zero() {
+        return 0;
+    }
+
+    let bps = |difference: U256| (difference * U256::from(10_000) / from).min(U256::from(i64::MAX)).as_u64() as i64;
+    if to >= from {
+        bps(to - from)
+    } else {
+        -bps(from - to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, fixture, mock_get};
+    use futures::StreamExt;
+    use std::sync::Mutex;
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const COLLECTION: &str = "0x1A92f7381B9F03921564a437210bB9396471050C";
+
+    fn eth(tenths: u64) -> U256 {
+        U256::exp10(17) * tenths
+    }
+
+    fn watcher() -> FloorWatcher {
+        let api = LooksRareApi::builder().base_url("http://localhost:1").build();
+        FloorWatcher::new(Arc::new(api)).watch(COLLECTION.parse().unwrap(), [eth(10)])
+    }
+
+    #[test]
+    fn reports_moves_against_the_last_reported_floor() {
+        let collection = COLLECTION.parse().unwrap();
+        let mut watcher = watcher();
+
+        assert!(watcher.evaluate(collection, eth(40), None).is_empty());
+        assert!(watcher.evaluate(collection, eth(41), None).is_empty());
+        assert_eq!(
+            watcher.evaluate(collection, eth(44), None),
+            [FloorEvent::Moved { collection, from: eth(40), to: eth(44), change_bps: 1_000 }]
+        );
+        assert_eq!(
+            watcher.evaluate(collection, eth(22), None),
+            [FloorEvent::Moved { collection, from: eth(44), to: eth(22), change_bps: -5_000 }]
+        );
+    }
+
+    #[test]
+    fn reports_threshold_crossings_and_stale_stats() {
+        let collection = COLLECTION.parse().unwrap();
+        let mut watcher = watcher().change_bps(10_000);
+
+        assert!(watcher.evaluate(collection, eth(11), None).is_empty());
+        assert_eq!(
+            watcher.evaluate(collection, eth(9), None),
+            [FloorEvent::Crossed { collection, threshold: eth(10), price: eth(9), direction: Direction::Down }]
+        );
+        assert_eq!(
+            watcher.evaluate(collection, eth(10), Some(eth(30))),
+            [
+                FloorEvent::Crossed { collection, threshold: eth(10), price: eth(10), direction: Direction::Up },
+                FloorEvent::Diverged { collection, lowest_ask: eth(10), stats_floor: eth(30) },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_a_divergence_once_until_it_changes() {
+        let collection = COLLECTION.parse().unwrap();
+        let mut watcher = watcher().change_bps(10_000);
+        let diverged = |lowest_ask, stats_floor| vec![FloorEvent::Diverged { collection, lowest_ask, stats_floor }];
+
+        assert_eq!(watcher.evaluate(collection, eth(20), Some(eth(60))), diverged(eth(20), eth(60)));
+        assert!(watcher.evaluate(collection, eth(20), Some(eth(60))).is_empty());
+        assert!(watcher.evaluate(collection, eth(21), Some(eth(65))).is_empty());
+        assert_eq!(watcher.evaluate(collection, eth(20), Some(eth(130))), diverged(eth(20), eth(130)));
+
+        assert!(watcher.evaluate(collection, eth(20), Some(eth(20))).is_empty());
+        assert_eq!(watcher.evaluate(collection, eth(20), Some(eth(130))), diverged(eth(20), eth(130)));
+    }
+
+    #[tokio::test]
+    async fn takes_the_lowest_price_per_item_of_the_page() {
+        let server = MockServer::start().await;
+        let mut page = fixture("v1/orders_page");
+        page["data"][2]["amount"] = "10".into();
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let collection: Address = COLLECTION.parse().unwrap();
+        mock_get(&server, "/api/v1/collections/stats", &[("address", &format!("{:?}", collection))], "v1/collections_stats").await;
+
+        let (lowest_ask, _) = floor(&api(&server), collection).await.unwrap();
+
+        assert_eq!(lowest_ask, Some(eth(3)));
+    }
+
+    #[tokio::test]
+    async fn reports_moves_without_collection_stats() {
+        let server = MockServer::start().await;
+        let mut cheaper = fixture("v1/orders_page");
+        cheaper["data"][0]["price"] = eth(5).to_string().into();
+        for page in [fixture("v1/orders_page"), cheaper] {
+            Mock::given(method("GET"))
+                .and(path("/api/v1/orders"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(page))
+                .up_to_n_times(1)
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/api/v1/collections/stats"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "message": null, "data": null })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let collection: Address = COLLECTION.parse().unwrap();
+        let mut watcher = FloorWatcher::new(Arc::new(api(&server))).watch(collection, []);
+
+        assert!(watcher.check().await.is_empty());
+        assert_eq!(
+            watcher.check().await,
+            [FloorEvent::Moved { collection, from: eth(10), to: eth(5), change_bps: -5_000 }]
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_events_to_every_sink() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[("sort", "PRICE_ASC"), ("pagination[first]", "150")], "v1/orders_page").await;
+        let collection: Address = COLLECTION.parse().unwrap();
+        mock_get(&server, "/api/v1/collections/stats", &[("address", &format!("{:?}", collection))], "v1/collections_stats").await;
+        let expected = FloorEvent::Diverged {
+            collection,
+            lowest_ask: eth(10),
+            stats_floor: U256::from_dec_str("1150000000000000000").unwrap(),
+        };
+        Mock::given(method("POST"))
+            .and(path("/hooks/floor"))
+            .and(body_json(serde_json::to_value(&expected).unwrap()))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let seen = Arc::new(Mutex::new(vec![]));
+        let recorded = seen.clone();
+        let mut watcher = FloorWatcher::new(Arc::new(api(&server)))
+            .watch(collection, [])
+            .sink(sender)
+            .sink(Callback(move |event: &FloorEvent| recorded.lock().unwrap().push(event.clone())))
+            .sink(Webhook::new(format!("{}/hooks/floor", server.uri()).parse().unwrap()));
+
+        let events = watcher.check().await;
+        drop(watcher);
+        assert_eq!(events, [expected]);
+        assert_eq!(receiver.collect::<Vec<_>>().await, events);
+        assert_eq!(*seen.lock().unwrap(), events);
+    }
+}
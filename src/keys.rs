@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use ethers_core::{
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature,
+    },
+    utils::hex,
+};
+use ethers_signers::{LocalWallet, Signer, WalletError};
+use std::fmt;
+use std::path::Path;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// A private key for signing orders and transactions.
+///
+/// The key is wiped when dropped, and so is the hex these loaders decode it
+/// from. Copies made elsewhere are not: the keystore decryption inside
+/// `ethers-signers`, and the process environment `from_env` reads, keep
+/// theirs. `Debug` shows only the address and there is no `Display`, so the
+/// key cannot end up in logs by accident.
+///
+/// It is deliberately not `Clone`, so there is one copy to wipe; move it
+/// into whatever needs to own it, e.g. a `SignerMiddleware`.
+///
+/// Using it is optional: the signing functions take any ethers [`Signer`],
+/// and this is one of them.
+pub struct SigningKey {
+    wallet: LocalWallet,
+}
+
+impl SigningKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SigningKeyError> {
+        if bytes.len() != 32 {
+            return Err(SigningKeyError::InvalidKey);
+        }
+
+        let wallet = LocalWallet::from_bytes(bytes).map_err(|_| SigningKeyError::InvalidKey)?;
+        Ok(Self { wallet })
+    }
+
+    /// Parses a hex-encoded key, with or without `0x`.
+    pub fn from_hex(key: &str) -> Result<Self, SigningKeyError> {
+        let key = key.trim();
+        let bytes = Zeroizing::new(hex::decode(key.strip_prefix("0x").unwrap_or(key)).map_err(|_| SigningKeyError::InvalidKey)?);
+        Self::from_bytes(&bytes)
+    }
+
+    /// Reads a hex-encoded key from the environment variable `name`.
+    pub fn from_env(name: &str) -> Result<Self, SigningKeyError> {
+        let key = Zeroizing::new(std::env::var(name).map_err(|_| SigningKeyError::MissingEnv {
+            name: name.to_string(),
+        })?);
+        Self::from_hex(&key)
+    }
+
+    /// Reads a hex-encoded key from a file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, SigningKeyError> {
+        let key = Zeroizing::new(std::fs::read_to_string(path)?);
+        Self::from_hex(&key)
+    }
+
+    /// Decrypts an encrypted JSON keystore.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_keystore(path: impl AsRef<Path>, password: impl AsRef<[u8]>) -> Result<Self, SigningKeyError> {
+        let wallet = LocalWallet::decrypt_keystore(path, password)?;
+        Ok(Self { wallet })
+    }
+
+    /// Decrypts an encrypted JSON keystore with the password in the
+    /// environment variable `password_env`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_keystore_env(path: impl AsRef<Path>, password_env: &str) -> Result<Self, SigningKeyError> {
+        let password = Zeroizing::new(std::env::var(password_env).map_err(|_| SigningKeyError::MissingEnv {
+            name: password_env.to_string(),
+        })?);
+        Self::from_keystore(path, password.as_bytes())
+    }
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("address", &self.wallet.address())
+            .field("chain_id", &self.wallet.chain_id())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Signer for SigningKey {
+    type Error = WalletError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(&self, message: S) -> Result<Signature, Self::Error> {
+        self.wallet.sign_message(message).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        self.wallet.sign_transaction(tx).await
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(&self, payload: &T) -> Result<Signature, Self::Error> {
+        self.wallet.sign_typed_data(payload).await
+    }
+
+    fn address(&self) -> Address {
+        self.wallet.address()
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.wallet.chain_id()
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        Self {
+            wallet: self.wallet.with_chain_id(chain_id),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SigningKeyError {
+    #[error("Environment variable not set: {name}")]
+    MissingEnv { name: String },
+    #[error("Invalid private key")]
+    InvalidKey,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Wallet(#[from] WalletError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(target_arch = "wasm32"))]
+    use ethers_core::rand::thread_rng;
+
+    const KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[test]
+    fn debug_hides_the_key() {
+        let key = SigningKey::from_hex(&format!("0x{}", KEY)).unwrap();
+        let printed = format!("{:?}", key);
+
+        assert!(printed.contains(&format!("{:?}", key.address())));
+        assert!(!printed.contains(KEY));
+        assert!(!printed.contains(&KEY[..16]));
+    }
+
+    #[test]
+    fn loads_from_env() {
+        std::env::set_var("LOOKSRARE_TEST_SIGNING_KEY", KEY);
+        let key = SigningKey::from_env("LOOKSRARE_TEST_SIGNING_KEY").unwrap();
+
+        assert_eq!(key.address(), KEY.parse::<LocalWallet>().unwrap().address());
+        assert!(matches!(
+            SigningKey::from_env("LOOKSRARE_TEST_UNSET_KEY"),
+            Err(SigningKeyError::MissingEnv { .. })
+        ));
+        assert!(matches!(SigningKey::from_hex("0x1234"), Err(SigningKeyError::InvalidKey)));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn loads_from_files_and_keystores() {
+        let dir = tempfile::tempdir().unwrap();
+        let address = KEY.parse::<LocalWallet>().unwrap().address();
+
+        let path = dir.path().join("key");
+        std::fs::write(&path, format!("0x{}\n", KEY)).unwrap();
+        assert_eq!(SigningKey::from_file(&path).unwrap().address(), address);
+        assert!(matches!(SigningKey::from_file(dir.path().join("missing")), Err(SigningKeyError::Io(_))));
+
+        let keystore = dir.path().join("keystore");
+        let key = hex::decode(KEY).unwrap();
+        LocalWallet::encrypt_keystore(dir.path(), &mut thread_rng(), key, "hunter2", Some("keystore")).unwrap();
+        assert_eq!(SigningKey::from_keystore(&keystore, "hunter2").unwrap().address(), address);
+        assert!(matches!(SigningKey::from_keystore(&keystore, "hunter3"), Err(SigningKeyError::Wallet(_))));
+
+        std::env::set_var("LOOKSRARE_TEST_KEYSTORE_PASSWORD", "hunter2");
+        let key = SigningKey::from_keystore_env(&keystore, "LOOKSRARE_TEST_KEYSTORE_PASSWORD").unwrap();
+        assert_eq!(key.address(), address);
+    }
+}
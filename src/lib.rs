@@ -1,10 +1,46 @@
 #![crate_type = "lib"]
 
+#[cfg(all(target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("building for wasm32 requires the `wasm` feature");
+
 pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod bulk;
+pub mod cache;
 pub mod constants;
+#[cfg(feature = "contracts")]
+pub mod contracts;
+pub mod feed;
+pub mod floor;
+pub mod keys;
+pub mod orderbook;
+pub mod rate_limit;
+#[cfg(feature = "contracts")]
+pub mod rewards;
+pub mod signing;
+#[cfg(feature = "contracts")]
+pub mod simulation;
+#[cfg(feature = "contracts")]
+pub mod staking;
+#[cfg(feature = "contracts")]
+pub mod sweep;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+#[cfg(feature = "contracts")]
+pub mod trades;
+pub mod transport;
 pub mod types;
+pub mod v2;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(test)]
+mod test_utils;
 
 use api::{
+    CreateOrderRequest,
     LooksRareApi, 
     LooksRareApiError, 
     OrdersRequest,
@@ -17,13 +53,17 @@ use api::{
 use types::{
     Account, 
     CollectionInformation,
+    CollectionRewardPoints,
     CollectionRewards,
     CollectionStats,
     Order,
+    RewardsEpoch,
+    RewardsProof,
+    UserRewards,
 };
 
 use thiserror::Error;
-use ethers::prelude::Address;
+use ethers_core::types::Address;
 
 pub async fn get_account(
     api: &LooksRareApi, 
@@ -80,6 +120,17 @@ pub async fn get_orders(
     Ok(orders)
 }
 
+pub async fn create_order(
+    api: &LooksRareApi,
+    req: CreateOrderRequest,
+) -> Result<Order, ClientError> {
+    let order = api
+        .create_order(req)
+        .await?;
+
+    Ok(order)
+}
+
 pub async fn get_nonce(
     api: &LooksRareApi, 
     address: Address,
@@ -125,6 +176,49 @@ pub async fn get_top_5_listing_rewards_collections(
     Ok(top_5_listing_rewards_collections)
 }
 
+pub async fn get_user_rewards(
+    api: &LooksRareApi,
+    address: Address,
+) -> Result<UserRewards, ClientError> {
+    let user_rewards = api
+        .get_user_rewards(address)
+        .await?;
+
+    Ok(user_rewards)
+}
+
+pub async fn get_collection_reward_points(
+    api: &LooksRareApi,
+    address: Address,
+) -> Result<Vec<CollectionRewardPoints>, ClientError> {
+    let collection_reward_points = api
+        .get_collection_reward_points(address)
+        .await?;
+
+    Ok(collection_reward_points)
+}
+
+pub async fn get_rewards_epochs(
+    api: &LooksRareApi,
+) -> Result<Vec<RewardsEpoch>, ClientError> {
+    let rewards_epochs = api
+        .get_rewards_epochs()
+        .await?;
+
+    Ok(rewards_epochs)
+}
+
+pub async fn get_rewards_proof(
+    api: &LooksRareApi,
+    address: Address,
+) -> Result<RewardsProof, ClientError> {
+    let rewards_proof = api
+        .get_rewards_proof(address)
+        .await?;
+
+    Ok(rewards_proof)
+}
+
 #[derive(Debug, Error)]
 pub enum ClientError {
     #[error(transparent)]
@@ -133,6 +227,138 @@ pub enum ClientError {
 
 #[cfg(test)]
 mod tests {
+    use crate::test_utils::{api, mock_get};
+    use wiremock::MockServer;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_account() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/accounts", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/accounts").await;
+        let api = api(&server);
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let account: Account = get_account(&api, input_address).await.unwrap();
+        assert_eq!(input_address, account.address);
+    }
+
+    #[tokio::test]
+    async fn can_get_orders() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[
+            ("isOrderAsk", "true"),
+            ("collection", "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258"),
+            ("tokenId", "62962"),
+            ("price[min]", "12000000000000000000000000000000000000"),
+            ("status[]", "CANCELLED"),
+            ("sort", "NEWEST"),
+        ], "v1/orders").await;
+        let api = api(&server);
+
+        let orders: Vec<Order> = get_orders(
+            &api,
+            Some(true),
+            Some("0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap()),
+            Some(62962),
+            None,
+            None,
+            None,
+            None,
+            Some(12000000000000000000000000000000000000),
+            None,
+            None,
+            None,
+            Some(vec![Status::Cancelled]),
+            None,
+            None,
+            Some(Sort::Newest),
+        ).await.unwrap();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].token_id, "62962");
+    }
+
+    #[tokio::test]
+    async fn can_get_nonce() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders/nonce", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/orders_nonce").await;
+        let api = api(&server);
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonce: u64 = get_nonce(&api, input_address).await.unwrap();
+        assert_eq!(nonce, 17833);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_information() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections", &[("address", "0x1a92f7381b9f03921564a437210bb9396471050c")], "v1/collections").await;
+        let api = api(&server);
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let collection_information: CollectionInformation = get_collection_information(&api, input_address).await.unwrap();
+        assert_eq!(input_address, collection_information.address);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_stats() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections/stats", &[("address", "0x1a92f7381b9f03921564a437210bb9396471050c")], "v1/collections_stats").await;
+        let api = api(&server);
+        let input_address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let collection_stats: CollectionStats = get_collection_stats(&api, input_address).await.unwrap();
+        assert_eq!(input_address, collection_stats.address);
+    }
+
+    #[tokio::test]
+    async fn can_get_top_5_listing_rewards_collections() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/collections/listing-rewards", &[], "v1/collections_listing_rewards").await;
+        let api = api(&server);
+        let top_5_listing_rewards_collections: Vec<CollectionRewards> = get_top_5_listing_rewards_collections(&api).await.unwrap();
+        assert_eq!(top_5_listing_rewards_collections.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn can_get_user_rewards() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards").await;
+        let api = api(&server);
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let user_rewards: UserRewards = get_user_rewards(&api, input_address).await.unwrap();
+        assert_eq!(input_address, user_rewards.address);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_reward_points() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/collections", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards_collections").await;
+        let api = api(&server);
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let collection_reward_points: Vec<CollectionRewardPoints> = get_collection_reward_points(&api, input_address).await.unwrap();
+        assert_eq!(collection_reward_points.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_epochs() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/epochs", &[], "v1/rewards_epochs").await;
+        let api = api(&server);
+        let rewards_epochs: Vec<RewardsEpoch> = get_rewards_epochs(&api).await.unwrap();
+        assert_eq!(rewards_epochs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_proof() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/rewards/proof", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v1/rewards_proof").await;
+        let api = api(&server);
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let rewards_proof: RewardsProof = get_rewards_proof(&api, input_address).await.unwrap();
+        assert_eq!(input_address, rewards_proof.address);
+    }
+}
+
+#[cfg(all(test, feature = "live-tests"))]
+mod live_tests {
     //use crate::types::Account;
 
     use super::*;
@@ -239,4 +465,37 @@ mod tests {
         let num_of_collections: usize = top_5_listing_rewards_collections.len();
         assert_eq!(num_of_collections, 5);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn can_get_user_rewards() {
+        let api = LooksRareApi::new();
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let user_rewards: UserRewards = get_user_rewards(&api, input_address).await.unwrap();
+        let output_address: Address = user_rewards.address;
+        assert_eq!(input_address, output_address);
+    }
+
+    #[tokio::test]
+    async fn can_get_collection_reward_points() {
+        let api = LooksRareApi::new();
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let collection_reward_points: Vec<CollectionRewardPoints> = get_collection_reward_points(&api, input_address).await.unwrap();
+        assert!(collection_reward_points.iter().all(|c| c.collection != Address::zero()));
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_epochs() {
+        let api = LooksRareApi::new();
+        let rewards_epochs: Vec<RewardsEpoch> = get_rewards_epochs(&api).await.unwrap();
+        assert!(rewards_epochs.windows(2).all(|w| w[0].epoch != w[1].epoch));
+    }
+
+    #[tokio::test]
+    async fn can_get_rewards_proof() {
+        let api = LooksRareApi::new();
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let rewards_proof: RewardsProof = get_rewards_proof(&api, input_address).await.unwrap();
+        let output_address: Address = rewards_proof.address;
+        assert_eq!(input_address, output_address);
+    }
+}
@@ -0,0 +1,225 @@
+use super::OrderBook;
+use crate::api::{EventsRequest, LooksRareApi, LooksRareApiError, Pagination};
+use crate::constants;
+use crate::types::{Event, EventType, Order};
+use ethers_core::types::Address;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures_timer::Delay;
+use instant::SystemTime;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A change applied to a [`LiveOrderBook`].
+#[derive(Clone, Debug)]
+pub enum BookUpdate {
+    Added(Order),
+    Removed { order: Order, reason: Removal },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Removal {
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+/// An [`OrderBook`] kept in sync with the events endpoint.
+///
+/// [`seed`](Self::seed) snapshots the valid orders of a collection; each
+/// [`poll`](Self::poll) then applies the listings, offers, sales and
+/// cancellations published since, drops expired orders and sends every
+/// change to the receiver returned by `seed`. Events replaying orders
+/// already in the book are ignored, so the snapshot and the events may
+/// overlap.
+pub struct LiveOrderBook {
+    api: Arc<LooksRareApi>,
+    collection: Address,
+    book: OrderBook,
+    /// Id of the newest event applied.
+    last_event: u64,
+    interval: Duration,
+    updates: UnboundedSender<BookUpdate>,
+}
+
+impl LiveOrderBook {
+    pub async fn seed(
+        api: Arc<LooksRareApi>,
+        collection: Address,
+    ) -> Result<(Self, UnboundedReceiver<BookUpdate>), LooksRareApiError> {
+        // Read the event position before the snapshot, so nothing published
+        // while the orders are paged through is missed.
+        let latest = api
+            .get_events(EventsRequest {
+                collection: Some(collection),
+                pagination: Some(Pagination { first: Some(1), cursor: None }),
+                ..Default::default()
+            })
+            .await?;
+        let book = OrderBook::fetch(&api, collection).await?;
+        let (updates, receiver) = mpsc::unbounded();
+
+        let live = Self {
+            api,
+            collection,
+            book,
+            last_event: latest.first().map(|event| event.id).unwrap_or_default(),
+            interval: Duration::from_secs(10),
+            updates,
+        };
+        Ok((live, receiver))
+    }
+
+    /// Time between polls in [`run`](Self::run). Defaults to 10 seconds.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+
+    /// Applies the events published since the last poll.
+    pub async fn poll(&mut self) -> Result<(), LooksRareApiError> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.poll_at(now).await
+    }
+
+    /// Polls every [`interval`](Self::interval) until the receiver is
+    /// dropped. Returns the first API error; the book keeps its state, so
+    /// calling `run` again resumes where it stopped.
+    pub async fn run(&mut self) -> Result<(), LooksRareApiError> {
+        while !self.updates.is_closed() {
+            self.poll().await?;
+            Delay::new(self.interval).await;
+        }
+        Ok(())
+    }
+
+    async fn poll_at(&mut self, now: u64) -> Result<(), LooksRareApiError> {
+        for event in self.new_events().await? {
+            self.last_event = self.last_event.max(event.id);
+            self.apply(event, now);
+        }
+        self.prune(now);
+        Ok(())
+    }
+
+    /// Events newer than `last_event`, oldest first.
+    async fn new_events(&self) -> Result<Vec<Event>, LooksRareApiError> {
+        let first = constants::MAX_EVENTS_PAGE_SIZE;
+        let mut events = vec![];
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .api
+                .get_events(EventsRequest {
+                    collection: Some(self.collection),
+                    pagination: Some(Pagination { first: Some(first), cursor }),
+                    ..Default::default()
+                })
+                .await?;
+            let full_page = page.len() as u64 == first;
+            cursor = page.last().map(|event| event.id.to_string());
+
+            let seen = events.len();
+            events.extend(page.into_iter().take_while(|event| event.id > self.last_event));
+
+            if !full_page || events.len() - seen < first as usize {
+                break;
+            }
+        }
+
+        events.reverse();
+        Ok(events)
+    }
+
+    fn apply(&mut self, event: Event, now: u64) {
+        let order = match event.order {
+            Some(order) => order,
+            None => return,
+        };
+
+        match event.type_ {
+            EventType::List | EventType::Offer => {
+                if order.end_time > now && self.book.insert(order.clone()) {
+                    self.publish(BookUpdate::Added(order));
+                }
+            }
+            EventType::Sale => self.take(&order.hash, Removal::Executed),
+            EventType::CancelList | EventType::CancelOffer => self.take(&order.hash, Removal::Cancelled),
+            EventType::Mint | EventType::Transfer => {}
+        }
+    }
+
+    fn take(&mut self, hash: &str, reason: Removal) {
+        if let Some(order) = self.book.remove(hash) {
+            self.publish(BookUpdate::Removed { order, reason });
+        }
+    }
+
+    fn prune(&mut self, now: u64) {
+        for order in self.book.remove_expired(now) {
+            self.publish(BookUpdate::Removed {
+                order,
+                reason: Removal::Expired,
+            });
+        }
+    }
+
+    fn publish(&self, update: BookUpdate) {
+        // Nobody listening is fine; the book itself stays up to date.
+        let _ = self.updates.unbounded_send(update);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, mock_get};
+    use futures::StreamExt;
+    use wiremock::MockServer;
+
+    const COLLECTION: &str = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258";
+
+    fn hash(update: &BookUpdate) -> (&str, Option<Removal>) {
+        match update {
+            BookUpdate::Added(order) => (&order.hash[64..], None),
+            BookUpdate::Removed { order, reason } => (&order.hash[64..], Some(*reason)),
+        }
+    }
+
+    #[tokio::test]
+    async fn applies_events_published_since_the_snapshot() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/events", &[("pagination[first]", "1")], "v1/events_latest").await;
+        mock_get(&server, "/api/v1/orders", &[("status[]", "VALID")], "v1/orders_page").await;
+        mock_get(&server, "/api/v1/events", &[("collection", COLLECTION), ("pagination[first]", "150")], "v1/events").await;
+
+        let (mut live, mut updates) = LiveOrderBook::seed(Arc::new(api(&server)), COLLECTION.parse().unwrap()).await.unwrap();
+        assert_eq!(live.book().len(), 4);
+
+        live.poll_at(1667750000).await.unwrap();
+        live.prune(1667754634);
+
+        updates.close();
+        let applied: Vec<BookUpdate> = updates.collect().await;
+        assert_eq!(
+            applied.iter().map(hash).collect::<Vec<_>>(),
+            [
+                ("b0", None),
+                ("a1", Some(Removal::Cancelled)),
+                ("a0", Some(Removal::Executed)),
+                ("a2", Some(Removal::Expired)),
+                ("a3", Some(Removal::Expired)),
+            ]
+        );
+        assert_eq!(live.last_event, 106);
+        assert_eq!(live.book().len(), 1);
+        assert_eq!(live.book().best_bid().unwrap().orders[0].hash[64..], *"b0");
+    }
+}
@@ -0,0 +1,460 @@
+use crate::api::{LooksRareApi, LooksRareApiError, OrdersRequest, Status};
+use crate::types::Order;
+use ethers_core::types::{Address, U256};
+use std::collections::{BTreeMap, HashMap};
+
+mod live;
+
+pub use live::{BookUpdate, LiveOrderBook, Removal};
+
+/// Side of the book: asks are listings, bids are offers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Ask,
+    Bid,
+}
+
+/// Orders resting at one price per item.
+#[derive(Clone, Debug)]
+pub struct PriceLevel {
+    /// Price per item, in wei of the order currency.
+    pub price: U256,
+    /// Items available at this price.
+    pub amount: u64,
+    pub orders: Vec<Order>,
+}
+
+/// A [`PriceLevel`] with running totals from the top of the book.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepthLevel {
+    pub price: U256,
+    pub amount: u64,
+    pub cumulative_amount: u64,
+    pub cumulative_value: U256,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Depth {
+    /// Cheapest first.
+    pub asks: Vec<DepthLevel>,
+    /// Highest first.
+    pub bids: Vec<DepthLevel>,
+}
+
+/// Cost of taking `items` items from one side of the book.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sweep {
+    pub items: u64,
+    pub total: U256,
+    pub average_price: U256,
+    /// Price of the last level reached.
+    pub worst_price: U256,
+    /// How far `average_price` is from the top of the book, in basis points.
+    pub price_impact_bps: u64,
+}
+
+/// Valid orders of a collection, grouped by price per item.
+///
+/// Prices are compared as raw amounts, so a book mixing currencies (e.g. ETH
+/// and WETH) is only meaningful when they share decimals. Levels are treated
+/// as divisible: a sweep may take part of a multi-item order.
+#[derive(Clone, Debug, Default)]
+pub struct OrderBook {
+    asks: BTreeMap<U256, PriceLevel>,
+    bids: BTreeMap<U256, PriceLevel>,
+    /// Side and level of every order, by hash.
+    index: HashMap<String, (Side, U256)>,
+}
+
+impl OrderBook {
+    pub fn from_orders(orders: impl IntoIterator<Item = Order>) -> Self {
+        let mut book = Self::default();
+        for order in orders {
+            book.insert(order);
+        }
+        book
+    }
+
+    /// Fetches every valid order of `collection`, page by page.
+    pub async fn fetch(api: &LooksRareApi, collection: Address) -> Result<Self, LooksRareApiError> {
+        Self::fetch_orders(api, collection, None).await
+    }
+
+    /// Fetches every valid order on one token of `collection`, e.g. to price
+    /// a single ERC-1155 id whose listings each hold several items.
+    pub async fn fetch_token(api: &LooksRareApi, collection: Address, token_id: u64) -> Result<Self, LooksRareApiError> {
+        Self::fetch_orders(api, collection, Some(token_id)).await
+    }
+
+    async fn fetch_orders(
+        api: &LooksRareApi,
+        collection: Address,
+        token_id: Option<u64>,
+    ) -> Result<Self, LooksRareApiError> {
+        let orders = api
+            .get_all_orders(OrdersRequest {
+                is_order_ask: None,
+                collection: Some(collection),
+                token_id,
+                signer: None,
+                nonce: None,
+                strategy: None,
+                currency: None,
+                price: None,
+                start_time: None,
+                end_time: None,
+                status: Some(vec![Status::Valid]),
+                pagination: None,
+                sort: None,
+            })
+            .await?;
+
+        Ok(Self::from_orders(orders))
+    }
+
+    /// The orders on `token_id` only, as a book of their own.
+    pub fn token(&self, token_id: u64) -> OrderBook {
+        let token_id = token_id.to_string();
+        Self::from_orders(
+            self.asks
+                .values()
+                .chain(self.bids.values())
+                .flat_map(|level| &level.orders)
+                .filter(|order| order.token_id == token_id)
+                .cloned(),
+        )
+    }
+
+    /// Adds `order` to its level. Returns `false`, leaving the book as is, for
+    /// orders already in the book, orders that are not `VALID` and orders
+    /// whose price or amount do not parse.
+    pub fn insert(&mut self, order: Order) -> bool {
+        if order.status != Status::Valid.to_str() || self.index.contains_key(&order.hash) {
+            return false;
+        }
+        let (unit_price, amount) = match (order.unit_price(), order.amount.parse::<u64>()) {
+            (Some(unit_price), Ok(amount)) => (unit_price, amount),
+            _ => return false,
+        };
+
+        let side = if order.is_order_ask { Side::Ask } else { Side::Bid };
+        let level = self.side_mut(side).entry(unit_price).or_insert_with(|| PriceLevel {
+            price: unit_price,
+            amount: 0,
+            orders: vec![],
+        });
+        level.amount += amount;
+        level.orders.push(order.clone());
+        self.index.insert(order.hash, (side, unit_price));
+        true
+    }
+
+    /// Takes the order with `hash` out of the book.
+    pub fn remove(&mut self, hash: &str) -> Option<Order> {
+        let (side, price) = self.index.remove(hash)?;
+        let levels = self.side_mut(side);
+        let level = levels.get_mut(&price)?;
+        let position = level.orders.iter().position(|order| order.hash == hash)?;
+        let order = level.orders.remove(position);
+
+        level.amount -= order.amount.parse::<u64>().unwrap_or_default();
+        if level.orders.is_empty() {
+            levels.remove(&price);
+        }
+        Some(order)
+    }
+
+    /// Takes out every order whose `end_time` is at or before `now`, in
+    /// seconds since the Unix epoch.
+    pub fn remove_expired(&mut self, now: u64) -> Vec<Order> {
+        let expired: Vec<String> = self
+            .asks
+            .values()
+            .chain(self.bids.values())
+            .flat_map(|level| &level.orders)
+            .filter(|order| order.end_time <= now)
+            .map(|order| order.hash.clone())
+            .collect();
+
+        expired.iter().filter_map(|hash| self.remove(hash)).collect()
+    }
+
+    pub fn contains(&self, hash: &str) -> bool {
+        self.index.contains_key(hash)
+    }
+
+    /// Number of orders in the book.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn side_mut(&mut self, side: Side) -> &mut BTreeMap<U256, PriceLevel> {
+        match side {
+            Side::Ask => &mut self.asks,
+            Side::Bid => &mut self.bids,
+        }
+    }
+
+    /// Listings, cheapest first.
+    pub fn asks(&self) -> impl Iterator<Item = &PriceLevel> {
+        self.asks.values()
+    }
+
+    /// Offers, highest first.
+    pub fn bids(&self) -> impl Iterator<Item = &PriceLevel> {
+        self.bids.values().rev()
+    }
+
+    pub fn levels(&self, side: Side) -> Box<dyn Iterator<Item = &PriceLevel> + '_> {
+        match side {
+            Side::Ask => Box::new(self.asks()),
+            Side::Bid => Box::new(self.bids()),
+        }
+    }
+
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks().next()
+    }
+
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids().next()
+    }
+
+    /// Best ask minus best bid; zero when the book is crossed.
+    pub fn spread(&self) -> Option<U256> {
+        Some(self.best_ask()?.price.saturating_sub(self.best_bid()?.price))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.asks.is_empty() && self.bids.is_empty()
+    }
+
+    /// The top `levels` levels of each side.
+    pub fn depth(&self, levels: usize) -> Depth {
+        Depth {
+            asks: depth(self.asks(), levels),
+            bids: depth(self.bids(), levels),
+        }
+    }
+
+    /// What taking `items` items from `side` would cost: buying listings for
+    /// [`Side::Ask`], selling into offers for [`Side::Bid`]. `None` if the
+    /// side holds fewer than `items` items.
+    pub fn price_impact(&self, side: Side, items: u64) -> Option<Sweep> {
+        if items == 0 {
+            return None;
+        }
+
+        let best_price = self.levels(side).next()?.price;
+
+        let mut remaining = items;
+        let mut total = U256::zero();
+        let mut worst_price = best_price;
+        for level in self.levels(side) {
+            let taken = remaining.min(level.amount);
+            total += level.price * taken;
+            worst_price = level.price;
+            remaining -= taken;
+            if remaining == 0 {
+                break;
+            }
+        }
+        if remaining > 0 {
+            return None;
+        }
+
+        let average_price = total / items;
+        let difference = if average_price > best_price { average_price - best_price } else { best_price - average_price };
+        let price_impact_bps = if best_price.is_zero() { 0 } else { (difference * U256::from(10_000) / best_price).low_u64() };
+
+        Some(Sweep {
+            items,
+            total,
+            average_price,
+            worst_price,
+            price_impact_bps,
+        })
+    }
+}
+
+fn depth<'a>(levels: impl Iterator<Item = &'a PriceLevel>, count: usize) -> Vec<DepthLevel> {
+    let mut cumulative_amount = 0;
+    let mut cumulative_value = U256::zero();
+
+    levels
+        .take(count)
+        .map(|level| {
+            cumulative_amount += level.amount;
+            cumulative_value += level.price * level.amount;
+            DepthLevel {
+                price: level.price,
+                amount: level.amount,
+                cumulative_amount,
+                cumulative_value,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, mock_get};
+    use wiremock::MockServer;
+
+    fn eth(n: u64) -> U256 {
+        U256::exp10(18) * n
+    }
+
+    fn order(hash: &str, is_order_ask: bool, price: U256, amount: u64) -> Order {
+        Order {
+            hash: hash.to_string(),
+            collection_address: Address::zero(),
+            token_id: String::from("1"),
+            is_order_ask,
+            signer: Address::zero(),
+            strategy: Address::zero(),
+            currency_address: Address::zero(),
+            amount: amount.to_string(),
+            price: price.to_string(),
+            nonce: String::from("0"),
+            start_time: 0,
+            end_time: 0,
+            min_percentage_to_ask: 8500,
+            params: String::new(),
+            status: String::from("VALID"),
+            signature: None,
+            v: None,
+            r: None,
+            s: None,
+        }
+    }
+
+    fn book() -> OrderBook {
+        OrderBook::from_orders([
+            order("0x1", true, eth(2), 1),
+            order("0x2", true, eth(1), 1),
+            order("0x3", true, eth(4), 2),
+            order("0x4", true, eth(1), 1),
+            order("0x5", false, eth(1) / 2, 1),
+            order("0x6", false, eth(3) / 4, 3),
+        ])
+    }
+
+    #[test]
+    fn groups_orders_by_price_per_item() {
+        let book = book();
+
+        let best_ask = book.best_ask().unwrap();
+        assert_eq!(best_ask.price, eth(1));
+        assert_eq!(best_ask.amount, 2);
+        assert_eq!(best_ask.orders.len(), 2);
+        assert_eq!(book.asks().map(|level| level.price).collect::<Vec<_>>(), [eth(1), eth(2)]);
+        assert_eq!(book.best_bid().unwrap().price, eth(1) / 2);
+        assert_eq!(book.spread(), Some(eth(1) / 2));
+    }
+
+    #[test]
+    fn skips_orders_that_are_not_valid() {
+        let mut executed = order("0x1", true, eth(1), 1);
+        executed.status = String::from("EXECUTED");
+        let mut book = OrderBook::from_orders([executed]);
+
+        assert!(book.is_empty());
+        assert!(!book.insert(order("0x2", true, eth(1), 0)));
+        assert_eq!(book.spread(), None);
+    }
+
+    #[test]
+    fn removes_orders_by_hash_and_expiry() {
+        let mut book = book();
+        let mut late = order("0x7", true, eth(1), 1);
+        late.end_time = 100;
+
+        assert!(book.insert(late));
+        assert!(!book.insert(order("0x2", true, eth(1), 1)));
+        assert_eq!(book.best_ask().unwrap().amount, 3);
+
+        assert_eq!(book.remove("0x2").unwrap().hash, "0x2");
+        assert!(book.remove("0x2").is_none());
+        assert_eq!(book.best_ask().unwrap().amount, 2);
+
+        let expired = book.remove_expired(50);
+        assert_eq!(expired.len(), 5);
+        assert_eq!(book.len(), 1);
+        assert_eq!(book.best_ask().unwrap().orders[0].hash, "0x7");
+        assert!(book.best_bid().is_none());
+    }
+
+    #[test]
+    fn depth_accumulates_from_the_top() {
+        let depth = book().depth(1);
+
+        assert_eq!(depth.asks, [DepthLevel { price: eth(1), amount: 2, cumulative_amount: 2, cumulative_value: eth(2) }]);
+        assert_eq!(book().depth(5).bids.last().unwrap().cumulative_value, eth(1) / 2 + eth(3) / 4);
+    }
+
+    #[test]
+    fn price_impact_of_sweeping_listings() {
+        let book = book();
+
+        let sweep = book.price_impact(Side::Ask, 4).unwrap();
+        assert_eq!(sweep.total, eth(6));
+        assert_eq!(sweep.average_price, eth(3) / 2);
+        assert_eq!(sweep.worst_price, eth(2));
+        assert_eq!(sweep.price_impact_bps, 5_000);
+
+        assert_eq!(book.price_impact(Side::Ask, 2).unwrap().price_impact_bps, 0);
+        assert_eq!(book.price_impact(Side::Bid, 4).unwrap().worst_price, eth(1) / 4);
+        assert!(book.price_impact(Side::Ask, 6).is_none());
+    }
+
+    #[test]
+    fn aggregates_erc1155_amounts_per_token() {
+        let on_token = |token_id: &str, mut order: Order| {
+            order.token_id = token_id.to_string();
+            order
+        };
+        let book = OrderBook::from_orders([
+            on_token("1", order("0xa", true, eth(10), 5)),
+            on_token("1", order("0xb", true, eth(6), 2)),
+            on_token("2", order("0xc", true, eth(3), 3)),
+            on_token("2", order("0xd", false, eth(2), 4)),
+        ]);
+
+        let first = book.token(1);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.asks().map(|level| (level.price, level.amount)).collect::<Vec<_>>(), [(eth(2), 5), (eth(3), 2)]);
+        assert!(first.best_bid().is_none());
+        assert_eq!(first.price_impact(Side::Ask, 6).unwrap().total, eth(13));
+
+        let second = book.token(2);
+        assert_eq!(second.best_ask().map(|level| (level.price, level.amount)), Some((eth(1), 3)));
+        assert_eq!(second.best_bid().map(|level| (level.price, level.amount)), Some((eth(1) / 2, 4)));
+        assert!(book.token(3).is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetches_valid_orders_of_a_collection() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[("status[]", "VALID"), ("pagination[first]", "150")], "v1/orders_page").await;
+
+        let collection = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap();
+        let book = OrderBook::fetch(&api(&server), collection).await.unwrap();
+
+        assert_eq!(book.asks().count(), 4);
+        assert_eq!(book.best_ask().unwrap().price, eth(1));
+        assert_eq!(book.best_bid().map(|level| level.price), None);
+    }
+
+    #[tokio::test]
+    async fn fetches_valid_orders_of_a_token() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v1/orders", &[("tokenId", "1002"), ("status[]", "VALID")], "v1/orders_page").await;
+
+        let collection = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap();
+        let book = OrderBook::fetch_token(&api(&server), collection, 1002).await.unwrap();
+
+        assert_eq!(book.token(1002).best_ask().unwrap().price, eth(3));
+    }
+}
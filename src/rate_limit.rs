@@ -0,0 +1,91 @@
+use futures_timer::Delay;
+use instant::Instant;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Spaces requests evenly so a client stays under the API's rate limit,
+/// and retries requests the API still rejects with `429 Too Many Requests`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    max_retries: u32,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RateLimiter {
+    /// Allows `requests` requests every `period`.
+    pub fn new(requests: u32, period: Duration) -> Self {
+        Self {
+            interval: period / requests.max(1),
+            max_retries: 3,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// How many times a request rejected with `429` is retried. Defaults to 3.
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub(crate) fn retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Waits for the next free slot and returns how long that took.
+    pub(crate) async fn acquire(&self) -> Duration {
+        let wait = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |next| next.max(now));
+            *next_slot = Some(slot + self.interval);
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            Delay::new(wait).await;
+        }
+
+        wait
+    }
+
+    /// Delay before retry number `attempt` when the API gave no `Retry-After`.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        self.interval.max(Duration::from_millis(250)) * 2u32.saturating_pow(attempt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn spaces_requests_by_interval() {
+        let limiter = RateLimiter::new(4, Duration::from_millis(200));
+        let start = Instant::now();
+
+        let mut waits = vec![];
+        for _ in 0..3 {
+            waits.push(limiter.acquire().await);
+        }
+
+        assert!(waits[0].is_zero());
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially() {
+        let limiter = RateLimiter::per_second(100);
+
+        assert_eq!(limiter.backoff(1), Duration::from_millis(500));
+        assert_eq!(limiter.backoff(2), Duration::from_millis(1000));
+    }
+}
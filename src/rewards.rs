@@ -0,0 +1,151 @@
+use crate::api::{LooksRareApi, LooksRareApiError};
+use crate::constants;
+use crate::contracts::TradingRewardsDistributor;
+use crate::types::RewardsProof;
+use ethers::{
+    abi::{encode_packed, Token},
+    contract::{ContractCall, ContractError},
+    prelude::{Address, Middleware},
+    types::{H256, U256},
+    utils::keccak256,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Claims LOOKS trading rewards from the merkle distributor.
+///
+/// Proofs are fetched from the API and checked against the root the
+/// distributor holds for its current round before a transaction is built.
+pub struct RewardsDistributor<M> {
+    contract: TradingRewardsDistributor<M>,
+}
+
+impl<M: Middleware> RewardsDistributor<M> {
+    pub fn new(address: Address, client: Arc<M>) -> Self {
+        Self {
+            contract: TradingRewardsDistributor::new(address, client),
+        }
+    }
+
+    pub fn mainnet(client: Arc<M>) -> Self {
+        let address: Address = constants::TRADING_REWARDS_DISTRIBUTOR_MAINNET.parse().unwrap();
+        Self::new(address, client)
+    }
+
+    pub fn contract(&self) -> &TradingRewardsDistributor<M> {
+        &self.contract
+    }
+
+    /// Merkle root of the round currently open for claims.
+    pub async fn current_root(&self) -> Result<H256, ClaimError<M>> {
+        let round = self.contract.current_reward_round().call().await?;
+        let root = self.contract.merkle_root_of_reward_round(round).call().await?;
+
+        Ok(H256::from(root))
+    }
+
+    /// Amount still claimable by `proof.address` given its cumulative `proof`.
+    pub async fn claimable(&self, proof: &RewardsProof) -> Result<U256, ClaimError<M>> {
+        let claimed = self.contract.amount_claimed_by_user(proof.address).call().await?;
+
+        Ok(proof.amount.saturating_sub(claimed))
+    }
+
+    /// Builds the `claim` transaction for `account`.
+    ///
+    /// Fails with [`ClaimError::InvalidProof`] if the API proof does not
+    /// resolve to the on-chain root, and with [`ClaimError::NothingToClaim`]
+    /// if every reward covered by the proof has already been claimed.
+    pub async fn claim(
+        &self,
+        api: &LooksRareApi,
+        account: Address,
+    ) -> Result<ContractCall<M, ()>, ClaimError<M>> {
+        let proof = api.get_rewards_proof(account).await?;
+        let root = self.current_root().await?;
+
+        if !verify_proof(&proof.proof, root, leaf(account, proof.amount)) {
+            return Err(ClaimError::InvalidProof { address: account, root });
+        }
+
+        if self.claimable(&proof).await?.is_zero() {
+            return Err(ClaimError::NothingToClaim { address: account });
+        }
+
+        let merkle_proof: Vec<[u8; 32]> = proof.proof.iter().map(|node| node.0).collect();
+        let call = self.contract.claim(proof.amount, merkle_proof).from(account);
+
+        Ok(call)
+    }
+}
+
+/// Leaf committed to by the distributor: `keccak256(abi.encodePacked(account, amount))`.
+pub fn leaf(account: Address, amount: U256) -> H256 {
+    let packed = encode_packed(&[Token::Address(account), Token::Uint(amount)]).unwrap();
+    H256::from(keccak256(packed))
+}
+
+/// Verifies `proof` the way OpenZeppelin's `MerkleProof` does, hashing each
+/// pair in sorted order.
+pub fn verify_proof(proof: &[H256], root: H256, leaf: H256) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| hash_pair(node, *sibling));
+    computed == root
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(first.as_bytes());
+    buf[32..].copy_from_slice(second.as_bytes());
+    H256::from(keccak256(buf))
+}
+
+#[derive(Debug, Error)]
+pub enum ClaimError<M: Middleware> {
+    #[error(transparent)]
+    LooksRareApiError(#[from] LooksRareApiError),
+    #[error(transparent)]
+    ContractError(#[from] ContractError<M>),
+    #[error("Merkle proof does not match root (address: {address}, root: {root:?})")]
+    InvalidProof { address: Address, root: H256 },
+    #[error("Nothing to claim (address: {address})")]
+    NothingToClaim { address: Address },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_sorted_pair_proof() {
+        let accounts: Vec<Address> = vec![
+            "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap(),
+            "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap(),
+            "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap(),
+        ];
+        let leaves: Vec<H256> = accounts
+            .iter()
+            .enumerate()
+            .map(|(i, a)| leaf(*a, U256::exp10(18) * (i + 1)))
+            .collect();
+
+        let left = hash_pair(leaves[0], leaves[1]);
+        let root = hash_pair(left, leaves[2]);
+
+        assert!(verify_proof(&[leaves[1], leaves[2]], root, leaves[0]));
+        assert!(verify_proof(&[leaves[0], leaves[2]], root, leaves[1]));
+        assert!(verify_proof(&[left], root, leaves[2]));
+    }
+
+    #[test]
+    fn rejects_wrong_amount() {
+        let account: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let other: Address = "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap();
+
+        let sibling = leaf(other, U256::from(5));
+        let root = hash_pair(leaf(account, U256::from(10)), sibling);
+
+        assert!(verify_proof(&[sibling], root, leaf(account, U256::from(10))));
+        assert!(!verify_proof(&[sibling], root, leaf(account, U256::from(11))));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::api::CreateOrderRequest;
+use crate::constants;
+use crate::types::{MakerOrder, Order};
+use ethers_core::{
+    abi::{encode, Token},
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712},
+        Address, Bytes, Signature, H256, U256,
+    },
+    utils::keccak256,
+};
+use ethers_signers::Signer;
+use std::convert::Infallible;
+use thiserror::Error;
+
+pub const DOMAIN_NAME: &str = "LooksRareExchange";
+pub const DOMAIN_VERSION: &str = "1";
+
+pub const MAKER_ORDER_TYPE: &str = "MakerOrder(bool isOrderAsk,address signer,address collection,uint256 price,uint256 tokenId,uint256 amount,address strategy,address currency,uint256 nonce,uint256 startTime,uint256 endTime,uint256 minPercentageToAsk,bytes params)";
+
+/// Signing domain of a `LooksRareExchange` deployment.
+pub fn domain(chain_id: u64, verifying_contract: Address) -> EIP712Domain {
+    EIP712Domain {
+        name: Some(DOMAIN_NAME.to_string()),
+        version: Some(DOMAIN_VERSION.to_string()),
+        chain_id: Some(U256::from(chain_id)),
+        verifying_contract: Some(verifying_contract),
+        salt: None,
+    }
+}
+
+pub fn mainnet_domain() -> EIP712Domain {
+    domain(1, constants::LOOKSRARE_EXCHANGE_MAINNET.parse().unwrap())
+}
+
+impl MakerOrder {
+    /// EIP-712 `hashStruct` of the order, i.e. the order hash the exchange
+    /// and the API use.
+    pub fn hash(&self) -> H256 {
+        H256::from(keccak256(encode(&[
+            Token::FixedBytes(keccak256(MAKER_ORDER_TYPE).to_vec()),
+            Token::Bool(self.is_order_ask),
+            Token::Address(self.signer),
+            Token::Address(self.collection),
+            Token::Uint(self.price),
+            Token::Uint(self.token_id),
+            Token::Uint(self.amount),
+            Token::Address(self.strategy),
+            Token::Address(self.currency),
+            Token::Uint(self.nonce),
+            Token::Uint(U256::from(self.start_time)),
+            Token::Uint(U256::from(self.end_time)),
+            Token::Uint(U256::from(self.min_percentage_to_ask)),
+            Token::FixedBytes(keccak256(&self.params).to_vec()),
+        ])))
+    }
+
+    /// The `POST /orders` body for this order signed with `signature`.
+    pub fn into_request(self, signature: Signature) -> CreateOrderRequest {
+        CreateOrderRequest {
+            signature: format!("0x{}", signature),
+            collection: self.collection,
+            token_id: self.token_id.to_string(),
+            signer: self.signer,
+            strategy: self.strategy,
+            currency: self.currency,
+            is_order_ask: self.is_order_ask,
+            nonce: self.nonce.to_string(),
+            amount: self.amount.to_string(),
+            price: self.price.to_string(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            min_percentage_to_ask: self.min_percentage_to_ask,
+            params: if self.params.is_empty() { String::new() } else { self.params.to_string() },
+        }
+    }
+}
+
+impl TryFrom<&CreateOrderRequest> for MakerOrder {
+    type Error = InvalidOrderRequest;
+
+    fn try_from(req: &CreateOrderRequest) -> Result<Self, Self::Error> {
+        Ok(MakerOrder {
+            is_order_ask: req.is_order_ask,
+            signer: req.signer,
+            collection: req.collection,
+            price: uint("price", &req.price)?,
+            token_id: uint("tokenId", &req.token_id)?,
+            amount: uint("amount", &req.amount)?,
+            strategy: req.strategy,
+            currency: req.currency,
+            nonce: uint("nonce", &req.nonce)?,
+            start_time: req.start_time,
+            end_time: req.end_time,
+            min_percentage_to_ask: req.min_percentage_to_ask,
+            params: params(&req.params)?,
+        })
+    }
+}
+
+/// The maker order behind an order returned by the API.
+impl TryFrom<&Order> for MakerOrder {
+    type Error = InvalidOrderRequest;
+
+    fn try_from(order: &Order) -> Result<Self, Self::Error> {
+        Ok(MakerOrder {
+            is_order_ask: order.is_order_ask,
+            signer: order.signer,
+            collection: order.collection_address,
+            price: uint("price", &order.price)?,
+            token_id: uint("tokenId", &order.token_id)?,
+            amount: uint("amount", &order.amount)?,
+            strategy: order.strategy,
+            currency: order.currency_address,
+            nonce: uint("nonce", &order.nonce)?,
+            start_time: order.start_time,
+            end_time: order.end_time,
+            min_percentage_to_ask: order.min_percentage_to_ask,
+            params: params(&order.params)?,
+        })
+    }
+}
+
+fn uint(field: &'static str, value: &str) -> Result<U256, InvalidOrderRequest> {
+    U256::from_dec_str(value).map_err(|_| InvalidOrderRequest { field, value: value.to_string() })
+}
+
+fn params(value: &str) -> Result<Bytes, InvalidOrderRequest> {
+    match value {
+        "" | "0x" => Ok(Bytes::default()),
+        params => params.parse().map_err(|_| InvalidOrderRequest {
+            field: "params",
+            value: params.to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid {field} in order request: {value:?}")]
+pub struct InvalidOrderRequest {
+    pub field: &'static str,
+    pub value: String,
+}
+
+/// A maker order bound to the domain it is signed for.
+#[derive(Clone, Debug)]
+pub struct TypedMakerOrder<'a> {
+    pub order: &'a MakerOrder,
+    pub domain: EIP712Domain,
+}
+
+impl<'a> Eip712 for TypedMakerOrder<'a> {
+    type Error = Infallible;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(self.domain.clone())
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(MAKER_ORDER_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(self.order.hash().0)
+    }
+}
+
+/// Signs a v1 maker order with any ethers [`Signer`], e.g. a
+/// [`SigningKey`](crate::keys::SigningKey), a `LocalWallet` or a hardware
+/// wallet.
+pub async fn sign_maker_order<S: Signer>(
+    signer: &S,
+    order: &MakerOrder,
+    domain: EIP712Domain,
+) -> Result<Signature, S::Error> {
+    signer.sign_typed_data(&TypedMakerOrder { order, domain }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_signers::LocalWallet;
+
+    fn order(signer: Address) -> MakerOrder {
+        MakerOrder {
+            is_order_ask: true,
+            signer,
+            collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+            price: U256::exp10(18),
+            token_id: U256::from(62962),
+            amount: U256::one(),
+            strategy: constants::STRATEGY_STANDARD_SALE_MAINNET.parse().unwrap(),
+            currency: constants::WETH_MAINNET.parse().unwrap(),
+            nonce: U256::from(17832),
+            start_time: 1667747434,
+            end_time: 1667754634,
+            min_percentage_to_ask: 8500,
+            params: Bytes::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn signature_recovers_order_signer() {
+        let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let order = order(wallet.address());
+        let domain = mainnet_domain();
+
+        let signature = sign_maker_order(&wallet, &order, domain.clone()).await.unwrap();
+        let digest = TypedMakerOrder { order: &order, domain }.encode_eip712().unwrap();
+
+        assert_eq!(signature.recover(H256::from(digest)).unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn request_round_trips() {
+        let order = order(Address::repeat_byte(1));
+        let req = order.clone().into_request(Signature { r: U256::one(), s: U256::one(), v: 27 });
+
+        assert_eq!(req.params, "");
+        assert_eq!(MakerOrder::try_from(&req).unwrap(), order);
+    }
+}
@@ -0,0 +1,229 @@
+//! Dry runs of transactions with `eth_call`, so a purchase that would revert
+//! (e.g. because someone else filled the order first) is caught before any
+//! gas is spent. Works against any node, including a local anvil fork.
+
+use async_trait::async_trait;
+use ethers::{
+    abi::{AbiDecode, Detokenize},
+    contract::{ContractCall, ContractError},
+    prelude::Middleware,
+    types::{transaction::eip2718::TypedTransaction, Bytes},
+    utils::id,
+};
+use thiserror::Error;
+
+/// Selector of Solidity's `Error(string)`.
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Why the exchange would reject a transaction.
+///
+/// Decoded from the v1 exchange's revert reasons and from the custom errors
+/// of the v2 protocol.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ExecutionError {
+    /// The maker order was already executed or cancelled.
+    #[error("Order: Matching order expired")]
+    OrderExpired,
+    /// The strategy refused the match, e.g. the order is outside its time
+    /// window or the taker's price or token differ.
+    #[error("Strategy: Execution invalid")]
+    ExecutionInvalid,
+    #[error("Signature: Invalid")]
+    InvalidSignature,
+    #[error("Order: Invalid signer")]
+    InvalidSigner,
+    #[error("Order: Amount cannot be 0")]
+    InvalidAmount,
+    #[error("Currency: Not whitelisted")]
+    CurrencyNotWhitelisted,
+    #[error("Strategy: Not whitelisted")]
+    StrategyNotWhitelisted,
+    #[error("Order: Taker must be the sender")]
+    TakerNotSender,
+    #[error("Order: Wrong sides")]
+    WrongSides,
+    #[error("Order: Msg.value too high")]
+    MsgValueTooHigh,
+    #[error("Order: Currency must be WETH")]
+    CurrencyNotWeth,
+    #[error("Fees: Higher than expected")]
+    FeesHigherThanExpected,
+    #[error("Transfer: No NFT transfer manager available")]
+    NoTransferManager,
+    #[error("Merkle proof invalid")]
+    MerkleProofInvalid,
+    /// Any other revert reason.
+    #[error("{0}")]
+    Reverted(String),
+    /// Revert data that is neither a reason nor a known custom error.
+    #[error("Reverted with data {0}")]
+    Unknown(Bytes),
+}
+
+impl ExecutionError {
+    pub fn from_reason(reason: &str) -> Self {
+        match reason {
+            "Order: Matching order expired" => ExecutionError::OrderExpired,
+            "Strategy: Execution invalid" => ExecutionError::ExecutionInvalid,
+            "Order: Invalid signer" => ExecutionError::InvalidSigner,
+            "Order: Amount cannot be 0" => ExecutionError::InvalidAmount,
+            "Currency: Not whitelisted" => ExecutionError::CurrencyNotWhitelisted,
+            "Strategy: Not whitelisted" => ExecutionError::StrategyNotWhitelisted,
+            "Order: Taker must be the sender" => ExecutionError::TakerNotSender,
+            "Order: Wrong sides" => ExecutionError::WrongSides,
+            "Order: Msg.value too high" => ExecutionError::MsgValueTooHigh,
+            "Order: Currency must be WETH" => ExecutionError::CurrencyNotWeth,
+            "Fees: Higher than expected" => ExecutionError::FeesHigherThanExpected,
+            "Transfer: No NFT transfer manager available" => ExecutionError::NoTransferManager,
+            reason if reason.starts_with("Signature: ") => ExecutionError::InvalidSignature,
+            reason => ExecutionError::Reverted(reason.to_string()),
+        }
+    }
+
+    /// Decodes the data a call reverted with.
+    pub fn from_revert_data(data: &Bytes) -> Self {
+        if data.len() < 4 {
+            return ExecutionError::Unknown(data.clone());
+        }
+
+        let (selector, args) = data.split_at(4);
+        if selector == ERROR_STRING_SELECTOR {
+            return match String::decode(args) {
+                Ok(reason) => Self::from_reason(&reason),
+                Err(_) => ExecutionError::Unknown(data.clone()),
+            };
+        }
+
+        let custom_errors = [
+            ("NoncesInvalid()", ExecutionError::OrderExpired),
+            ("OutsideOfTimeRange()", ExecutionError::ExecutionInvalid),
+            ("SignatureEOAInvalid()", ExecutionError::InvalidSignature),
+            ("SignatureParameterSInvalid()", ExecutionError::InvalidSignature),
+            ("SignatureParameterVInvalid(uint8)", ExecutionError::InvalidSignature),
+            ("SignatureLengthInvalid(uint256)", ExecutionError::InvalidSignature),
+            ("NullSignerAddress()", ExecutionError::InvalidSigner),
+            ("AmountInvalid()", ExecutionError::InvalidAmount),
+            ("CurrencyInvalid()", ExecutionError::CurrencyNotWhitelisted),
+            ("StrategyNotAvailable(uint256)", ExecutionError::StrategyNotWhitelisted),
+            ("CallerInvalid()", ExecutionError::TakerNotSender),
+            ("QuoteTypeInvalid()", ExecutionError::WrongSides),
+            ("MerkleProofInvalid()", ExecutionError::MerkleProofInvalid),
+        ];
+        custom_errors
+            .into_iter()
+            .find(|(signature, _)| id(signature) == selector)
+            .map(|(_, error)| error)
+            .unwrap_or_else(|| ExecutionError::Unknown(data.clone()))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SimulationError<M: Middleware> {
+    /// The transaction would revert.
+    #[error("Transaction would revert: {0}")]
+    Reverted(ExecutionError),
+    #[error(transparent)]
+    ContractError(ContractError<M>),
+}
+
+impl<M: Middleware> From<ContractError<M>> for SimulationError<M> {
+    fn from(e: ContractError<M>) -> Self {
+        match e.as_revert() {
+            Some(data) => SimulationError::Reverted(ExecutionError::from_revert_data(data)),
+            None => SimulationError::ContractError(e),
+        }
+    }
+}
+
+/// Runs a transaction builder's output through `eth_call` first.
+///
+/// Implemented for every [`ContractCall`] the crate builds, e.g. sweep
+/// purchases, reward claims and staking deposits.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Simulate<M: Middleware> {
+    type Output;
+
+    /// What the transaction would return if mined on the latest block (or
+    /// the block set on the call), without sending it.
+    async fn simulate(&self) -> Result<Self::Output, SimulationError<M>>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<M, D> Simulate<M> for ContractCall<M, D>
+where
+    M: Middleware,
+    D: Detokenize + Send + Sync,
+{
+    type Output = D;
+
+    async fn simulate(&self) -> Result<D, SimulationError<M>> {
+        Ok(self.call().await?)
+    }
+}
+
+/// Simulates a raw transaction, e.g. one of a sweep's aggregator calls,
+/// returning its output data.
+pub async fn simulate_transaction<M: Middleware>(client: &M, tx: &TypedTransaction) -> Result<Bytes, SimulationError<M>> {
+    client
+        .call(tx, None)
+        .await
+        .map_err(|e| ContractError::<M>::from_middleware_error(e).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::LooksRareExchange;
+    use ethers::{
+        abi::AbiEncode,
+        prelude::Address,
+        providers::{JsonRpcError, MockResponse, Provider},
+        types::U256,
+    };
+    use std::sync::Arc;
+
+    fn revert_data(reason: &str) -> Bytes {
+        [ERROR_STRING_SELECTOR.to_vec(), reason.to_string().encode()].concat().into()
+    }
+
+    #[test]
+    fn decodes_revert_reasons_and_custom_errors() {
+        assert_eq!(
+            ExecutionError::from_revert_data(&revert_data("Order: Matching order expired")),
+            ExecutionError::OrderExpired
+        );
+        assert_eq!(
+            ExecutionError::from_revert_data(&revert_data("Signature: Invalid v parameter")),
+            ExecutionError::InvalidSignature
+        );
+        assert_eq!(
+            ExecutionError::from_revert_data(&revert_data("ERC721: transfer from incorrect owner")),
+            ExecutionError::Reverted(String::from("ERC721: transfer from incorrect owner"))
+        );
+        assert_eq!(
+            ExecutionError::from_revert_data(&Bytes::from(id("NoncesInvalid()").to_vec())),
+            ExecutionError::OrderExpired
+        );
+        assert_eq!(
+            ExecutionError::from_revert_data(&Bytes::from(vec![1, 2])),
+            ExecutionError::Unknown(Bytes::from(vec![1, 2]))
+        );
+    }
+
+    #[tokio::test]
+    async fn simulation_surfaces_execution_errors() {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response(MockResponse::Error(JsonRpcError {
+            code: 3,
+            message: String::from("execution reverted: Strategy: Execution invalid"),
+            data: Some(serde_json::Value::String(revert_data("Strategy: Execution invalid").to_string())),
+        }));
+        let exchange = LooksRareExchange::new(Address::repeat_byte(1), Arc::new(provider));
+
+        let result = exchange.cancel_all_orders_for_sender(U256::one()).simulate().await;
+
+        assert!(matches!(result, Err(SimulationError::Reverted(ExecutionError::ExecutionInvalid))));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::constants;
+use crate::contracts::{AggregatorFeeSharingWithUniswapV3, FeeSharingSystem, LooksRareToken};
+use ethers::{
+    contract::{ContractCall, ContractError},
+    prelude::{Address, Middleware},
+    types::U256,
+};
+use std::sync::Arc;
+
+/// LOOKS staking through the fee-sharing system and its auto-compounder.
+pub struct LooksRareStaking<M> {
+    token: LooksRareToken<M>,
+    fee_sharing: FeeSharing<M>,
+    auto_compounder: AutoCompounder<M>,
+}
+
+impl<M: Middleware> LooksRareStaking<M> {
+    pub fn new(
+        token: Address,
+        fee_sharing: Address,
+        auto_compounder: Address,
+        client: Arc<M>,
+    ) -> Self {
+        Self {
+            token: LooksRareToken::new(token, client.clone()),
+            fee_sharing: FeeSharing::new(fee_sharing, client.clone()),
+            auto_compounder: AutoCompounder::new(auto_compounder, client),
+        }
+    }
+
+    pub fn mainnet(client: Arc<M>) -> Self {
+        Self::new(
+            constants::LOOKS_TOKEN_MAINNET.parse().unwrap(),
+            constants::FEE_SHARING_SYSTEM_MAINNET.parse().unwrap(),
+            constants::AGGREGATOR_FEE_SHARING_MAINNET.parse().unwrap(),
+            client,
+        )
+    }
+
+    pub fn token(&self) -> &LooksRareToken<M> {
+        &self.token
+    }
+
+    pub fn fee_sharing(&self) -> &FeeSharing<M> {
+        &self.fee_sharing
+    }
+
+    pub fn auto_compounder(&self) -> &AutoCompounder<M> {
+        &self.auto_compounder
+    }
+
+    pub async fn balance_of(&self, user: Address) -> Result<U256, ContractError<M>> {
+        self.token.balance_of(user).call().await
+    }
+
+    /// Approves the fee-sharing system to pull `amount` LOOKS on deposit.
+    pub fn approve_fee_sharing(&self, amount: U256) -> ContractCall<M, bool> {
+        self.token.approve(self.fee_sharing.contract.address(), amount)
+    }
+
+    /// Approves the auto-compounder to pull `amount` LOOKS on deposit.
+    pub fn approve_auto_compounder(&self, amount: U256) -> ContractCall<M, bool> {
+        self.token.approve(self.auto_compounder.contract.address(), amount)
+    }
+}
+
+/// A user's position in the fee-sharing system.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StakingPosition {
+    pub shares: U256,
+    pub user_reward_per_token_paid: U256,
+    pub rewards: U256,
+}
+
+/// `FeeSharingSystem`: stake LOOKS, earn LOOKS emissions plus WETH fees.
+pub struct FeeSharing<M> {
+    contract: FeeSharingSystem<M>,
+}
+
+impl<M: Middleware> FeeSharing<M> {
+    pub fn new(address: Address, client: Arc<M>) -> Self {
+        Self {
+            contract: FeeSharingSystem::new(address, client),
+        }
+    }
+
+    pub fn contract(&self) -> &FeeSharingSystem<M> {
+        &self.contract
+    }
+
+    pub fn deposit(&self, amount: U256, claim_reward_token: bool) -> ContractCall<M, ()> {
+        self.contract.deposit(amount, claim_reward_token)
+    }
+
+    pub fn withdraw(&self, shares: U256, claim_reward_token: bool) -> ContractCall<M, ()> {
+        self.contract.withdraw(shares, claim_reward_token)
+    }
+
+    pub fn withdraw_all(&self, claim_reward_token: bool) -> ContractCall<M, ()> {
+        self.contract.withdraw_all(claim_reward_token)
+    }
+
+    /// Claims pending WETH rewards.
+    pub fn harvest(&self) -> ContractCall<M, ()> {
+        self.contract.harvest()
+    }
+
+    /// Pending WETH rewards for `user`.
+    pub async fn pending_rewards(&self, user: Address) -> Result<U256, ContractError<M>> {
+        self.contract.calculate_pending_rewards(user).call().await
+    }
+
+    /// Price of one share in LOOKS, scaled by 1e18.
+    pub async fn share_price(&self) -> Result<U256, ContractError<M>> {
+        self.contract.calculate_share_price_in_looks().call().await
+    }
+
+    /// Value of `user`'s shares in LOOKS.
+    pub async fn staked_value(&self, user: Address) -> Result<U256, ContractError<M>> {
+        self.contract.calculate_shares_value_in_looks(user).call().await
+    }
+
+    pub async fn position(&self, user: Address) -> Result<StakingPosition, ContractError<M>> {
+        let (shares, user_reward_per_token_paid, rewards) = self.contract.user_info(user).call().await?;
+
+        Ok(StakingPosition {
+            shares,
+            user_reward_per_token_paid,
+            rewards,
+        })
+    }
+}
+
+/// `AggregatorFeeSharingWithUniswapV3`: sells WETH rewards for LOOKS and
+/// restakes them, so there is nothing to harvest per user.
+pub struct AutoCompounder<M> {
+    contract: AggregatorFeeSharingWithUniswapV3<M>,
+}
+
+impl<M: Middleware> AutoCompounder<M> {
+    pub fn new(address: Address, client: Arc<M>) -> Self {
+        Self {
+            contract: AggregatorFeeSharingWithUniswapV3::new(address, client),
+        }
+    }
+
+    pub fn contract(&self) -> &AggregatorFeeSharingWithUniswapV3<M> {
+        &self.contract
+    }
+
+    pub fn deposit(&self, amount: U256) -> ContractCall<M, ()> {
+        self.contract.deposit(amount)
+    }
+
+    pub fn withdraw(&self, shares: U256) -> ContractCall<M, ()> {
+        self.contract.withdraw(shares)
+    }
+
+    pub fn withdraw_all(&self) -> ContractCall<M, ()> {
+        self.contract.withdraw_all()
+    }
+
+    /// Sells accumulated WETH and compounds it for every depositor.
+    pub fn harvest(&self) -> ContractCall<M, ()> {
+        self.contract.harvest_and_sell_and_compound()
+    }
+
+    /// Price of one share in LOOKS, scaled by 1e18.
+    pub async fn share_price(&self) -> Result<U256, ContractError<M>> {
+        self.contract.calculate_share_price_in_looks().call().await
+    }
+
+    /// Value of `user`'s shares in LOOKS.
+    pub async fn staked_value(&self, user: Address) -> Result<U256, ContractError<M>> {
+        self.contract.calculate_shares_value_in_looks(user).call().await
+    }
+
+    pub async fn shares(&self, user: Address) -> Result<U256, ContractError<M>> {
+        self.contract.user_info(user).call().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::Token,
+        providers::{Http, Provider},
+    };
+
+    fn spender(call: ContractCall<Provider<Http>, bool>) -> Token {
+        let data = call.tx.data().unwrap();
+        call.function.decode_input(&data[4..]).unwrap().remove(0)
+    }
+
+    #[test]
+    fn approvals_target_staking_contracts() {
+        let provider = Arc::new(Provider::<Http>::try_from("http://localhost:8545").unwrap());
+        let staking = LooksRareStaking::mainnet(provider);
+
+        let fee_sharing: Address = constants::FEE_SHARING_SYSTEM_MAINNET.parse().unwrap();
+        let auto_compounder: Address = constants::AGGREGATOR_FEE_SHARING_MAINNET.parse().unwrap();
+
+        assert_eq!(spender(staking.approve_fee_sharing(U256::one())), Token::Address(fee_sharing));
+        assert_eq!(spender(staking.approve_auto_compounder(U256::one())), Token::Address(auto_compounder));
+    }
+}
@@ -0,0 +1,614 @@
+use crate::api::{LooksRareApi, LooksRareApiError, OrdersRequest, Pagination, Sort, Status};
+use crate::constants;
+use crate::contracts::{self, ExecutionStrategy, LooksRareExchange, RoyaltyFeeManager, TakerOrder, ERC1155, ERC721};
+use crate::signing::InvalidOrderRequest;
+use crate::simulation::{Simulate, SimulationError};
+use crate::types::{MakerOrder, Order};
+use ethers::{
+    contract::{ContractCall, ContractError},
+    prelude::{Address, Middleware},
+    types::{Bytes, Signature, U256},
+};
+use futures::future::join_all;
+use instant::SystemTime;
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// ERC-165 interface id of ERC-1155.
+const ERC1155_INTERFACE_ID: [u8; 4] = [0xd9, 0xb6, 0x7a, 0x26];
+
+/// Contracts a [`Sweeper`] reads from and buys through.
+#[derive(Clone, Debug)]
+pub struct ExchangeAddresses {
+    pub exchange: Address,
+    pub royalty_fee_manager: Address,
+    pub transfer_manager_erc721: Address,
+    pub transfer_manager_erc1155: Address,
+    pub strategy_standard_sale: Address,
+    pub weth: Address,
+}
+
+impl ExchangeAddresses {
+    pub fn mainnet() -> Self {
+        Self {
+            exchange: constants::LOOKSRARE_EXCHANGE_MAINNET.parse().unwrap(),
+            royalty_fee_manager: constants::ROYALTY_FEE_MANAGER_MAINNET.parse().unwrap(),
+            transfer_manager_erc721: constants::TRANSFER_MANAGER_ERC721_MAINNET.parse().unwrap(),
+            transfer_manager_erc1155: constants::TRANSFER_MANAGER_ERC1155_MAINNET.parse().unwrap(),
+            strategy_standard_sale: constants::STRATEGY_STANDARD_SALE_MAINNET.parse().unwrap(),
+            weth: constants::WETH_MAINNET.parse().unwrap(),
+        }
+    }
+}
+
+/// One `matchAskWithTakerBidUsingETHAndWETH` call of a [`SweepPlan`].
+pub struct Purchase<M> {
+    pub order: Order,
+    /// Cut of the price going to the protocol.
+    pub protocol_fee: U256,
+    /// Cut of the price going to the collection's royalty recipient.
+    pub royalty_fee: U256,
+    /// The purchase, paying `order.price` in ETH.
+    pub call: ContractCall<M, ()>,
+}
+
+/// A call for a multicall-style aggregator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Call {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// The purchases buying the cheapest fillable listings of a collection,
+/// cheapest first.
+///
+/// The buyer pays `total_price`; protocol and royalty fees are taken out of
+/// the sellers' proceeds.
+pub struct SweepPlan<M> {
+    pub purchases: Vec<Purchase<M>>,
+    /// Listings passed over, with the reason.
+    pub skipped: Vec<(Order, Unfillable)>,
+    pub items: u64,
+    pub total_price: U256,
+    pub protocol_fees: U256,
+    pub royalty_fees: U256,
+}
+
+impl<M: Middleware> SweepPlan<M> {
+    /// Simulates every purchase on its own, in plan order.
+    ///
+    /// Each call runs against the current chain state, so a purchase that
+    /// only fails because an earlier one in the plan drained the buyer's
+    /// balance still passes.
+    pub async fn simulate(&self) -> Vec<Result<(), SimulationError<M>>> {
+        join_all(self.purchases.iter().map(|purchase| purchase.call.simulate())).await
+    }
+
+    /// The purchases as plain calls, for batching through an aggregator.
+    pub fn batch(&self) -> Vec<Call> {
+        self.purchases
+            .iter()
+            .map(|purchase| Call {
+                to: purchase.call.tx.to_addr().copied().unwrap_or_default(),
+                value: purchase.call.tx.value().copied().unwrap_or_default(),
+                data: purchase.call.tx.data().cloned().unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Why a listing cannot be bought.
+#[derive(Debug, Error)]
+pub enum Unfillable {
+    #[error("Order not started yet")]
+    NotStarted,
+    #[error("Order expired")]
+    Expired,
+    #[error("Token already bought earlier in the sweep")]
+    DuplicateToken,
+    #[error("Order nonce executed or cancelled")]
+    NonceUsed,
+    #[error("Signer no longer holds the token")]
+    NotOwned,
+    #[error("Signer has not approved the transfer manager")]
+    NotApproved,
+    #[error("Fees exceed the seller's minimum percentage to ask")]
+    FeesAboveMinimum,
+    #[error(transparent)]
+    Invalid(#[from] InvalidOrderRequest),
+}
+
+/// Plans floor sweeps against the v1 `LooksRareExchange`.
+///
+/// Only standard-sale listings priced in WETH are considered; they are
+/// bought with ETH through `matchAskWithTakerBidUsingETHAndWETH`.
+pub struct Sweeper<M> {
+    addresses: ExchangeAddresses,
+    exchange: LooksRareExchange<M>,
+    royalty_fee_manager: RoyaltyFeeManager<M>,
+    client: Arc<M>,
+}
+
+impl<M: Middleware> Sweeper<M> {
+    pub fn new(addresses: ExchangeAddresses, client: Arc<M>) -> Self {
+        Self {
+            exchange: LooksRareExchange::new(addresses.exchange, client.clone()),
+            royalty_fee_manager: RoyaltyFeeManager::new(addresses.royalty_fee_manager, client.clone()),
+            addresses,
+            client,
+        }
+    }
+
+    pub fn mainnet(client: Arc<M>) -> Self {
+        Self::new(ExchangeAddresses::mainnet(), client)
+    }
+
+    pub fn exchange(&self) -> &LooksRareExchange<M> {
+        &self.exchange
+    }
+
+    /// Plans buying up to `count` items of `collection` for `buyer`,
+    /// spending at most `max_total_price` wei.
+    ///
+    /// Listings are taken cheapest per item first and checked on chain:
+    /// nonce still open, signer still holding the token and having approved
+    /// the transfer manager, and fees within the seller's minimum percentage
+    /// to ask. The plan may hold fewer than `count` items if not enough
+    /// listings pass within the budget.
+    ///
+    /// ERC-721 listings are fetched a page at a time as the sweep goes;
+    /// ERC-1155 listings are all fetched up front to order them per item.
+    pub async fn sweep(
+        &self,
+        api: &LooksRareApi,
+        buyer: Address,
+        collection: Address,
+        count: u64,
+        max_total_price: U256,
+    ) -> Result<SweepPlan<M>, SweepError<M>> {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        let erc1155 = self.is_erc1155(collection).await?;
+
+        let mut plan = SweepPlan {
+            purchases: vec![],
+            skipped: vec![],
+            items: 0,
+            total_price: U256::zero(),
+            protocol_fees: U256::zero(),
+            royalty_fees: U256::zero(),
+        };
+        let mut bought = HashSet::new();
+        let mut cursor = None;
+
+        'pages: loop {
+            let (page, last_page) = if erc1155 {
+                // The API sorts by total price, which puts multi-item listings
+                // out of unit price order; take them all and sort here.
+                let mut listings = api.get_all_orders(self.listings(collection, None)).await?;
+                listings.sort_by_key(|order| order.unit_price().unwrap_or(U256::MAX));
+                (listings, true)
+            } else {
+                let page = api.get_orders(self.listings(collection, cursor.take())).await?;
+                let last_page = (page.len() as u64) < constants::MAX_ORDERS_PAGE_SIZE;
+                cursor = page.last().map(|order| order.hash.clone());
+                (page, last_page || cursor.is_none())
+            };
+
+            for order in page {
+                if plan.items >= count {
+                    break 'pages;
+                }
+
+                let maker = match precheck(&order, now, &bought, erc1155) {
+                    Ok(maker) => maker,
+                    Err(reason) => {
+                        plan.skipped.push((order, reason));
+                        continue;
+                    }
+                };
+                if plan.total_price + maker.price > max_total_price {
+                    if erc1155 {
+                        // A dearer item in a smaller listing may still fit.
+                        continue;
+                    }
+                    // Listings come cheapest first, so none of the rest fit either.
+                    break 'pages;
+                }
+                if plan.items + maker.amount.low_u64() > count {
+                    continue;
+                }
+
+                match self.check(&maker, erc1155).await? {
+                    Ok((protocol_fee, royalty_fee)) => {
+                        let call = self.purchase(&order, &maker, buyer)?;
+                        bought.insert(maker.token_id);
+                        plan.items += maker.amount.low_u64();
+                        plan.total_price += maker.price;
+                        plan.protocol_fees += protocol_fee;
+                        plan.royalty_fees += royalty_fee;
+                        plan.purchases.push(Purchase {
+                            order,
+                            protocol_fee,
+                            royalty_fee,
+                            call,
+                        });
+                    }
+                    Err(reason) => plan.skipped.push((order, reason)),
+                }
+            }
+
+            if last_page {
+                break;
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn listings(&self, collection: Address, cursor: Option<String>) -> OrdersRequest {
+        OrdersRequest {
+            is_order_ask: Some(true),
+            collection: Some(collection),
+            token_id: None,
+            signer: None,
+            nonce: None,
+            strategy: Some(self.addresses.strategy_standard_sale),
+            currency: Some(self.addresses.weth),
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: Some(vec![Status::Valid]),
+            pagination: Some(Pagination {
+                first: Some(constants::MAX_ORDERS_PAGE_SIZE),
+                cursor,
+            }),
+            sort: Some(Sort::PriceAsc),
+        }
+    }
+
+    async fn is_erc1155(&self, collection: Address) -> Result<bool, SweepError<M>> {
+        let nft = ERC721::new(collection, self.client.clone());
+        match nft.supports_interface(ERC1155_INTERFACE_ID).call().await {
+            Ok(supported) => Ok(supported),
+            Err(ContractError::Revert(_)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// On-chain checks; on success, the protocol and royalty fees.
+    async fn check(&self, maker: &MakerOrder, erc1155: bool) -> Result<Result<(U256, U256), Unfillable>, SweepError<M>> {
+        let nonce_used = self
+            .exchange
+            .is_user_order_nonce_executed_or_cancelled(maker.signer, maker.nonce)
+            .call()
+            .await?;
+        let min_nonce = self.exchange.user_min_order_nonce(maker.signer).call().await?;
+        if nonce_used || maker.nonce < min_nonce {
+            return Ok(Err(Unfillable::NonceUsed));
+        }
+
+        let (owned, approved) = if erc1155 {
+            let nft = ERC1155::new(maker.collection, self.client.clone());
+            let balance = nft.balance_of(maker.signer, maker.token_id).call().await?;
+            let approved = nft
+                .is_approved_for_all(maker.signer, self.addresses.transfer_manager_erc1155)
+                .call()
+                .await?;
+            (balance >= maker.amount, approved)
+        } else {
+            let nft = ERC721::new(maker.collection, self.client.clone());
+            let owner = match nft.owner_of(maker.token_id).call().await {
+                Ok(owner) => Some(owner),
+                Err(ContractError::Revert(_)) => None,
+                Err(e) => return Err(e.into()),
+            };
+            let approved = nft
+                .is_approved_for_all(maker.signer, self.addresses.transfer_manager_erc721)
+                .call()
+                .await?;
+            (owner == Some(maker.signer), approved)
+        };
+        if !owned {
+            return Ok(Err(Unfillable::NotOwned));
+        }
+        if !approved {
+            return Ok(Err(Unfillable::NotApproved));
+        }
+
+        let protocol_fee_bps = ExecutionStrategy::new(maker.strategy, self.client.clone())
+            .view_protocol_fee()
+            .call()
+            .await?;
+        let protocol_fee = maker.price * protocol_fee_bps / 10_000;
+        let (_, royalty_fee) = self
+            .royalty_fee_manager
+            .calculate_royalty_fee_and_get_recipient(maker.collection, maker.token_id, maker.price)
+            .call()
+            .await?;
+
+        let proceeds = maker.price.saturating_sub(protocol_fee + royalty_fee);
+        if proceeds * 10_000 < maker.price * maker.min_percentage_to_ask {
+            return Ok(Err(Unfillable::FeesAboveMinimum));
+        }
+
+        Ok(Ok((protocol_fee, royalty_fee)))
+    }
+
+    fn purchase(&self, order: &Order, maker: &MakerOrder, buyer: Address) -> Result<ContractCall<M, ()>, SweepError<M>> {
+        let taker = TakerOrder {
+            is_order_ask: false,
+            taker: buyer,
+            price: maker.price,
+            token_id: maker.token_id,
+            min_percentage_to_ask: maker.min_percentage_to_ask.into(),
+            params: Bytes::default(),
+        };
+
+        Ok(self
+            .exchange
+            .match_ask_with_taker_bid_using_eth_and_weth(taker, maker_ask(order, maker)?)
+            .value(maker.price)
+            .from(buyer))
+    }
+}
+
+/// Checks that need no chain access.
+fn precheck(order: &Order, now: u64, bought: &HashSet<U256>, erc1155: bool) -> Result<MakerOrder, Unfillable> {
+    let maker = MakerOrder::try_from(order)?;
+
+    if maker.start_time > now {
+        return Err(Unfillable::NotStarted);
+    }
+    if maker.end_time < now {
+        return Err(Unfillable::Expired);
+    }
+    if !erc1155 && bought.contains(&maker.token_id) {
+        return Err(Unfillable::DuplicateToken);
+    }
+
+    Ok(maker)
+}
+
+/// `maker` in the exchange's calldata layout, signed as `order` was.
+fn maker_ask(order: &Order, maker: &MakerOrder) -> Result<contracts::MakerOrder, InvalidOrderRequest> {
+    let signature = match (&order.signature, order.v, order.r, order.s) {
+        (_, Some(v), Some(r), Some(s)) => Signature {
+            r: U256::from_big_endian(r.as_bytes()),
+            s: U256::from_big_endian(s.as_bytes()),
+            v: v.into(),
+        },
+        (Some(signature), ..) => signature.parse().map_err(|_| InvalidOrderRequest {
+            field: "signature",
+            value: signature.clone(),
+        })?,
+        _ => {
+            return Err(InvalidOrderRequest {
+                field: "signature",
+                value: String::new(),
+            })
+        }
+    };
+
+    let mut r = [0u8; 32];
+    let mut s = [0u8; 32];
+    signature.r.to_big_endian(&mut r);
+    signature.s.to_big_endian(&mut s);
+
+    Ok(contracts::MakerOrder {
+        is_order_ask: maker.is_order_ask,
+        signer: maker.signer,
+        collection: maker.collection,
+        price: maker.price,
+        token_id: maker.token_id,
+        amount: maker.amount,
+        strategy: maker.strategy,
+        currency: maker.currency,
+        nonce: maker.nonce,
+        start_time: maker.start_time.into(),
+        end_time: maker.end_time.into(),
+        min_percentage_to_ask: maker.min_percentage_to_ask.into(),
+        params: maker.params.clone(),
+        v: signature.v as u8,
+        r,
+        s,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum SweepError<M: Middleware> {
+    #[error(transparent)]
+    LooksRareApiError(#[from] LooksRareApiError),
+    #[error(transparent)]
+    ContractError(#[from] ContractError<M>),
+    #[error(transparent)]
+    InvalidOrder(#[from] InvalidOrderRequest),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{api, fixture};
+    use ethers::{
+        abi::{encode, AbiDecode, Token},
+        providers::{MockProvider, Provider},
+    };
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    const COLLECTION: &str = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258";
+    const SIGNER: &str = "0x1A92f7381B9F03921564a437210bB9396471050C";
+
+    fn eth(n: u64) -> U256 {
+        U256::exp10(18) * n
+    }
+
+    fn listing() -> Order {
+        serde_json::from_value(fixture("v1/orders_page")["data"][0].clone()).unwrap()
+    }
+
+    /// Serves the first fixture listings, still open, each with a `(price
+    /// in ETH, amount)`. Their token ids start at 1000.
+    async fn serve_listings(server: &MockServer, listings: &[(u64, u64)]) {
+        let mut page = fixture("v1/orders_page");
+        let orders = page["data"].as_array_mut().unwrap();
+        orders.truncate(listings.len());
+        for (order, (price, amount)) in orders.iter_mut().zip(listings) {
+            order["price"] = eth(*price).to_string().into();
+            order["amount"] = amount.to_string().into();
+            order["endTime"] = u32::MAX.into();
+        }
+
+        Mock::given(method("GET"))
+            .and(path("/api/v1/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    /// A provider answering `eth_call`s with `outputs`, in order.
+    fn provider(outputs: Vec<Vec<Token>>) -> Provider<MockProvider> {
+        let (provider, mock) = Provider::mocked();
+        // The mock answers with the last response pushed first.
+        for output in outputs.iter().rev() {
+            mock.push::<Bytes, _>(Bytes::from(encode(output))).unwrap();
+        }
+        provider
+    }
+
+    /// Outputs of the checks of a listing passing them all; `holding` is the
+    /// ERC-721 owner or the signer's ERC-1155 balance.
+    fn fillable(holding: Token) -> Vec<Vec<Token>> {
+        vec![
+            vec![Token::Bool(false)],
+            vec![Token::Uint(U256::zero())],
+            vec![holding],
+            vec![Token::Bool(true)],
+            vec![Token::Uint(U256::from(200))],
+            vec![Token::Address(Address::zero()), Token::Uint(U256::zero())],
+        ]
+    }
+
+    async fn sweep(
+        server: &MockServer,
+        provider: Provider<MockProvider>,
+        count: u64,
+        max_total_price: U256,
+    ) -> SweepPlan<Provider<MockProvider>> {
+        Sweeper::mainnet(Arc::new(provider))
+            .sweep(&api(server), Address::repeat_byte(0xb0), COLLECTION.parse().unwrap(), count, max_total_price)
+            .await
+            .unwrap()
+    }
+
+    fn token_ids<M>(plan: &SweepPlan<M>) -> Vec<&str> {
+        plan.purchases.iter().map(|purchase| purchase.order.token_id.as_str()).collect()
+    }
+
+    #[test]
+    fn prechecks_time_window_and_duplicates() {
+        let order = listing();
+        let mut bought = HashSet::new();
+
+        assert!(precheck(&order, order.start_time, &bought, false).is_ok());
+        assert!(matches!(precheck(&order, order.start_time - 1, &bought, false), Err(Unfillable::NotStarted)));
+        assert!(matches!(precheck(&order, order.end_time + 1, &bought, false), Err(Unfillable::Expired)));
+
+        bought.insert(U256::from(1000));
+        assert!(matches!(precheck(&order, order.start_time, &bought, false), Err(Unfillable::DuplicateToken)));
+        assert!(precheck(&order, order.start_time, &bought, true).is_ok());
+    }
+
+    #[test]
+    fn builds_eth_purchase_calls() {
+        let (provider, _mock) = Provider::mocked();
+        let sweeper = Sweeper::mainnet(Arc::new(provider));
+        let buyer = Address::repeat_byte(0xb0);
+        let order = listing();
+        let maker = MakerOrder::try_from(&order).unwrap();
+
+        let call = sweeper.purchase(&order, &maker, buyer).unwrap();
+        let plan = SweepPlan {
+            purchases: vec![Purchase { order, protocol_fee: U256::zero(), royalty_fee: U256::zero(), call }],
+            skipped: vec![],
+            items: 1,
+            total_price: maker.price,
+            protocol_fees: U256::zero(),
+            royalty_fees: U256::zero(),
+        };
+        let batch = plan.batch();
+
+        assert_eq!(batch[0].to, ExchangeAddresses::mainnet().exchange);
+        assert_eq!(batch[0].value, U256::exp10(18));
+        let decoded = contracts::MatchAskWithTakerBidUsingETHAndWETHCall::decode(&batch[0].data).unwrap();
+        assert_eq!(decoded.taker_bid.taker, buyer);
+        assert_eq!(decoded.maker_ask.token_id, U256::from(1000));
+        assert_eq!(decoded.maker_ask.v, 27);
+        assert_eq!(decoded.maker_ask.r, [0x22; 32]);
+    }
+
+    #[tokio::test]
+    async fn sweep_skips_used_nonces_and_stops_at_count() {
+        let server = MockServer::start().await;
+        serve_listings(&server, &[(1, 1), (2, 1), (3, 1)]).await;
+        let mut outputs = vec![vec![Token::Bool(false)]];
+        // The first listing's nonce was cancelled.
+        outputs.extend([vec![Token::Bool(true)], vec![Token::Uint(U256::zero())]]);
+        outputs.extend(fillable(Token::Address(SIGNER.parse().unwrap())));
+
+        // The third listing is never checked: its calls would find no output.
+        let plan = sweep(&server, provider(outputs), 1, eth(100)).await;
+
+        assert_eq!(token_ids(&plan), ["1001"]);
+        assert!(matches!(plan.skipped[..], [(_, Unfillable::NonceUsed)]));
+        assert_eq!(plan.items, 1);
+        assert_eq!(plan.total_price, eth(2));
+        assert_eq!(plan.protocol_fees, eth(2) * 200 / 10_000);
+    }
+
+    #[tokio::test]
+    async fn sweep_skips_sold_tokens_and_stops_at_the_budget() {
+        let server = MockServer::start().await;
+        serve_listings(&server, &[(1, 1), (2, 1), (3, 1)]).await;
+        let mut outputs = vec![vec![Token::Bool(false)]];
+        outputs.extend(fillable(Token::Address(SIGNER.parse().unwrap())));
+        // The second listing's token changed hands.
+        outputs.extend([
+            vec![Token::Bool(false)],
+            vec![Token::Uint(U256::zero())],
+            vec![Token::Address(Address::repeat_byte(0x99))],
+            vec![Token::Bool(true)],
+        ]);
+
+        let plan = sweep(&server, provider(outputs), 10, eth(7) / 2).await;
+
+        assert_eq!(token_ids(&plan), ["1000"]);
+        assert!(matches!(plan.skipped[..], [(_, Unfillable::NotOwned)]));
+        assert_eq!(plan.total_price, eth(1));
+    }
+
+    #[tokio::test]
+    async fn sweep_takes_erc1155_listings_by_price_per_item() {
+        let server = MockServer::start().await;
+        // By total price, as the API sorts them: 3, 1 and 2 ETH per item.
+        serve_listings(&server, &[(3, 1), (3, 3), (4, 2)]).await;
+        let mut outputs = vec![vec![Token::Bool(true)]];
+        outputs.extend(fillable(Token::Uint(U256::from(5))));
+        outputs.extend(fillable(Token::Uint(U256::from(5))));
+
+        // The 2 ETH per item listing is over budget, the smaller one after it is not.
+        let plan = sweep(&server, provider(outputs), 10, eth(6)).await;
+
+        assert_eq!(token_ids(&plan), ["1001", "1000"]);
+        assert!(plan.skipped.is_empty());
+        assert_eq!(plan.items, 4);
+        assert_eq!(plan.total_price, eth(6));
+    }
+}
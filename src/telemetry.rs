@@ -0,0 +1,167 @@
+//! Client usage metrics, recorded through the [`metrics`] facade.
+//!
+//! Install any `metrics` exporter (e.g. `metrics-exporter-prometheus`) to
+//! collect them:
+//!
+//! - `looksrare_requests_total` (counter; `method`, `endpoint`, `status`):
+//!   HTTP requests sent, `status` being `error` when no response arrived.
+//! - `looksrare_request_duration_seconds` (histogram; `method`, `endpoint`):
+//!   time until the final response, including rate-limit waits and retries.
+//! - `looksrare_errors_total` (counter; `endpoint`, `error`): failed API
+//!   calls by [`LooksRareApiError`] variant.
+//! - `looksrare_rate_limit_wait_seconds` (histogram; `endpoint`): time spent
+//!   waiting for the rate limiter.
+//! - `looksrare_rate_limit_retries_total` (counter; `endpoint`): requests
+//!   retried after `429 Too Many Requests`.
+
+use crate::api::{LooksRareApiError, SendStats};
+use crate::transport::HttpResponse;
+use metrics::{counter, describe_counter, describe_histogram, histogram, Unit};
+use reqwest::{Method, Url};
+use std::time::Duration;
+
+pub const REQUESTS: &str = "looksrare_requests_total";
+pub const REQUEST_DURATION: &str = "looksrare_request_duration_seconds";
+pub const ERRORS: &str = "looksrare_errors_total";
+pub const RATE_LIMIT_WAIT: &str = "looksrare_rate_limit_wait_seconds";
+pub const RATE_LIMIT_RETRIES: &str = "looksrare_rate_limit_retries_total";
+
+/// Registers units and descriptions with the installed recorder.
+pub fn describe_metrics() {
+    describe_counter!(REQUESTS, Unit::Count, "HTTP requests sent to the LooksRare API");
+    describe_histogram!(REQUEST_DURATION, Unit::Seconds, "Latency of LooksRare API requests");
+    describe_counter!(ERRORS, Unit::Count, "Failed LooksRare API calls");
+    describe_histogram!(RATE_LIMIT_WAIT, Unit::Seconds, "Time spent waiting for the rate limiter");
+    describe_counter!(RATE_LIMIT_RETRIES, Unit::Count, "Requests retried after 429 Too Many Requests");
+}
+
+pub(crate) fn record_request(
+    method: &Method,
+    endpoint: &str,
+    result: &Result<HttpResponse, LooksRareApiError>,
+    latency: Duration,
+    stats: &SendStats,
+) {
+    let status = match result {
+        Ok(res) => res.status.as_str().to_string(),
+        Err(_) => String::from("error"),
+    };
+
+    counter!(REQUESTS, "method" => method.to_string(), "endpoint" => endpoint.to_string(), "status" => status).increment(1);
+    histogram!(REQUEST_DURATION, "method" => method.to_string(), "endpoint" => endpoint.to_string())
+        .record(latency.as_secs_f64());
+
+    if !stats.rate_limit_wait.is_zero() {
+        histogram!(RATE_LIMIT_WAIT, "endpoint" => endpoint.to_string()).record(stats.rate_limit_wait.as_secs_f64());
+    }
+    if stats.retries > 0 {
+        counter!(RATE_LIMIT_RETRIES, "endpoint" => endpoint.to_string()).increment(stats.retries.into());
+    }
+}
+
+/// `url` is the request URL without query, so `endpoint` matches the label
+/// [`record_request`] uses.
+pub(crate) fn record_error(url: &str, error: &LooksRareApiError) {
+    let endpoint = Url::parse(url).map(|url| url.path().to_string()).unwrap_or_default();
+
+    counter!(ERRORS, "endpoint" => endpoint, "error" => error.kind()).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::LooksRareApi;
+    use crate::rate_limit::RateLimiter;
+    use crate::test_utils::mock_get;
+    use crate::transport::{HttpRequest, Transport, TransportError};
+    use ethers_core::types::Address;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    struct FailingTransport;
+
+    #[async_trait::async_trait]
+    impl Transport for FailingTransport {
+        async fn send(&self, _req: HttpRequest) -> Result<HttpResponse, TransportError> {
+            Err("connection refused".into())
+        }
+    }
+
+    #[test]
+    fn records_requests_retries_and_errors() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let address: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+
+        // The recorder is local to this thread, so the client runs on a
+        // current-thread runtime inside it.
+        metrics::with_local_recorder(&recorder, || {
+            describe_metrics();
+            tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(async {
+                let server = MockServer::start().await;
+                Mock::given(method("GET"))
+                    .and(path("/api/v1/orders/nonce"))
+                    .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+                    .up_to_n_times(2)
+                    .expect(2)
+                    .mount(&server)
+                    .await;
+                mock_get(&server, "/api/v1/orders/nonce", &[], "v1/orders_nonce").await;
+                Mock::given(method("GET"))
+                    .and(path("/api/v1/collections/stats"))
+                    .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "success": true, "message": null, "data": null })))
+                    .expect(1)
+                    .mount(&server)
+                    .await;
+                let api = LooksRareApi::builder()
+                    .base_url(server.uri())
+                    .rate_limiter(RateLimiter::per_second(50))
+                    .build();
+
+                api.get_nonce(address).await.unwrap();
+                assert!(api.get_collection_stats(address).await.is_err());
+
+                let offline = LooksRareApi::builder().transport(FailingTransport).build();
+                assert!(offline.get_collection_stats(address).await.is_err());
+            });
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let metric = |name: &str, labels: &[(&str, &str)]| {
+            snapshot
+                .iter()
+                .find(|(key, ..)| {
+                    key.key().name() == name
+                        && labels.iter().all(|(k, v)| key.key().labels().any(|l| l.key() == *k && l.value() == *v))
+                })
+                .map(|(_, unit, _, value)| (*unit, value))
+        };
+
+        let nonce = [("method", "GET"), ("endpoint", "/api/v1/orders/nonce")];
+        assert_eq!(
+            metric(REQUESTS, &[nonce[0], nonce[1], ("status", "200")]),
+            Some((Some(Unit::Count), &DebugValue::Counter(1)))
+        );
+        assert_eq!(metric(RATE_LIMIT_RETRIES, &nonce[1..]).map(|(_, value)| value), Some(&DebugValue::Counter(2)));
+        assert!(matches!(
+            metric(REQUEST_DURATION, &nonce[..]),
+            Some((Some(Unit::Seconds), DebugValue::Histogram(values))) if values.len() == 1
+        ));
+
+        assert_eq!(
+            metric(ERRORS, &[("endpoint", "/api/v1/collections/stats"), ("error", "collection_not_found")]),
+            Some((Some(Unit::Count), &DebugValue::Counter(1)))
+        );
+        assert_eq!(
+            metric(REQUESTS, &[("endpoint", "/api/v1/collections/stats"), ("status", "error")]).map(|(_, value)| value),
+            Some(&DebugValue::Counter(1))
+        );
+        assert_eq!(
+            metric(ERRORS, &[("endpoint", "/api/v1/collections/stats"), ("error", "transport")]).map(|(_, value)| value),
+            Some(&DebugValue::Counter(1))
+        );
+    }
+}
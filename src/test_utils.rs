@@ -0,0 +1,36 @@
+use crate::api::LooksRareApi;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock,
+    MockServer,
+    ResponseTemplate,
+};
+
+/// Loads a recorded API response from `fixtures/<name>.json`.
+pub(crate) fn fixture(name: &str) -> serde_json::Value {
+    let path = format!("{}/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    serde_json::from_str(&text).unwrap()
+}
+
+/// Serves `fixture` for exactly one `GET endpoint` carrying every `query` pair.
+pub(crate) async fn mock_get(
+    server: &MockServer,
+    endpoint: &str,
+    query: &[(&str, &str)],
+    fixture_name: &str,
+) {
+    let mut mock = Mock::given(method("GET")).and(path(endpoint));
+    for (key, value) in query {
+        mock = mock.and(query_param(*key, *value));
+    }
+
+    mock.respond_with(ResponseTemplate::new(200).set_body_json(fixture(fixture_name)))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+pub(crate) fn api(server: &MockServer) -> LooksRareApi {
+    LooksRareApi::builder().base_url(server.uri()).build()
+}
@@ -0,0 +1,450 @@
+//! An in-process fake of the LooksRare API for testing code built on this
+//! crate without touching the network.
+//!
+//! ```no_run
+//! # async fn example() {
+//! use looksrare::testing::FakeLooksRare;
+//!
+//! let fake = FakeLooksRare::start().await;
+//! let api = fake.api();
+//! # }
+//! ```
+
+use crate::api::{CreateOrderRequest, LooksRareApi};
+use crate::constants;
+use crate::types::{Account, CollectionInformation, CollectionStats, MakerOrder, Order};
+use ethers_core::types::{Address, BigEndianHash, Signature, H256, U256};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use wiremock::{
+    http::{Method, Url},
+    matchers::any,
+    Mock,
+    MockServer,
+    Request,
+    Respond,
+    ResponseTemplate,
+};
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 150;
+
+/// Fake LooksRare server backed by an in-memory order book.
+///
+/// Serves `GET /accounts`, `GET /orders`, `GET /orders/nonce`,
+/// `GET /collections`, `GET /collections/stats` and `POST /orders`. Orders are
+/// cancelled on-chain on the real marketplace, so cancellation goes through
+/// [`FakeLooksRare::cancel_orders`] and [`FakeLooksRare::cancel_all_orders`].
+pub struct FakeLooksRare {
+    server: MockServer,
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeLooksRare {
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let state = Arc::new(Mutex::new(State::default()));
+
+        Mock::given(any())
+            .respond_with(Router { state: state.clone() })
+            .mount(&server)
+            .await;
+
+        Self { server, state }
+    }
+
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A client pointed at this server.
+    pub fn api(&self) -> LooksRareApi {
+        LooksRareApi::builder().base_url(self.uri()).build()
+    }
+
+    pub fn insert_account(&self, account: Account) {
+        self.state.lock().unwrap().accounts.insert(account.address, account);
+    }
+
+    pub fn insert_collection(&self, collection: CollectionInformation) {
+        self.state.lock().unwrap().collections.insert(collection.address, collection);
+    }
+
+    pub fn insert_collection_stats(&self, stats: CollectionStats) {
+        self.state.lock().unwrap().collection_stats.insert(stats.address, stats);
+    }
+
+    /// Adds an order as is, bypassing `POST /orders` validation.
+    pub fn insert_order(&self, order: Order) {
+        self.state.lock().unwrap().orders.push(order);
+    }
+
+    /// Every order in the book, oldest first.
+    pub fn orders(&self) -> Vec<Order> {
+        self.state.lock().unwrap().orders.clone()
+    }
+
+    /// Cancels `signer`'s valid orders with the given nonces, like
+    /// `cancelMultipleMakerOrders`. Returns how many orders were cancelled.
+    pub fn cancel_orders(&self, signer: Address, nonces: &[u64]) -> usize {
+        self.state.lock().unwrap().cancel(|order| {
+            order.signer == signer && nonce_of(order).is_some_and(|n| nonces.contains(&n))
+        })
+    }
+
+    /// Cancels `signer`'s valid orders below `min_nonce`, like
+    /// `cancelAllOrdersForSender`. Returns how many orders were cancelled.
+    pub fn cancel_all_orders(&self, signer: Address, min_nonce: u64) -> usize {
+        self.state.lock().unwrap().cancel(|order| {
+            order.signer == signer && nonce_of(order).is_some_and(|n| n < min_nonce)
+        })
+    }
+}
+
+#[derive(Default)]
+struct State {
+    accounts: HashMap<Address, Account>,
+    collections: HashMap<Address, CollectionInformation>,
+    collection_stats: HashMap<Address, CollectionStats>,
+    orders: Vec<Order>,
+}
+
+impl State {
+    fn cancel(&mut self, matches: impl Fn(&Order) -> bool) -> usize {
+        let mut cancelled = 0;
+        for order in self.orders.iter_mut().filter(|o| o.status == "VALID" && matches(o)) {
+            order.status = String::from("CANCELLED");
+            cancelled += 1;
+        }
+        cancelled
+    }
+
+    fn next_nonce(&self, signer: Address) -> u64 {
+        self.orders
+            .iter()
+            .filter(|o| o.signer == signer)
+            .filter_map(nonce_of)
+            .max()
+            .map_or(0, |n| n + 1)
+    }
+
+    fn query_orders(&self, url: &Url) -> Result<Vec<Order>, String> {
+        let statuses = params(url, "status[]");
+        let min_price = param(url, "price[min]").map(|p| parse_u256(&p)).transpose()?;
+        let max_price = param(url, "price[max]").map(|p| parse_u256(&p)).transpose()?;
+
+        let mut orders: Vec<&Order> = self.orders.iter().rev().collect();
+
+        if let Some(is_order_ask) = param(url, "isOrderAsk") { orders.retain(|o| o.is_order_ask.to_string() == is_order_ask); };
+        if let Some(collection) = param_address(url, "collection")? { orders.retain(|o| o.collection_address == collection); };
+        if let Some(token_id) = param(url, "tokenId") { orders.retain(|o| o.token_id == token_id); };
+        if let Some(signer) = param_address(url, "signer")? { orders.retain(|o| o.signer == signer); };
+        if let Some(nonce) = param(url, "nonce") { orders.retain(|o| o.nonce == nonce); };
+        if let Some(strategy) = param_address(url, "strategy")? { orders.retain(|o| o.strategy == strategy); };
+        if let Some(currency) = param_address(url, "currency")? { orders.retain(|o| o.currency_address == currency); };
+        if let Some(start_time) = param(url, "startTime") { orders.retain(|o| o.start_time.to_string() == start_time); };
+        if let Some(end_time) = param(url, "endTime") { orders.retain(|o| o.end_time.to_string() == end_time); };
+        if !statuses.is_empty() { orders.retain(|o| statuses.contains(&o.status)); };
+        if min_price.is_some() || max_price.is_some() {
+            orders.retain(|o| {
+                let price = U256::from_dec_str(&o.price).unwrap_or_default();
+                !matches!(min_price, Some(min) if price < min) && !matches!(max_price, Some(max) if price > max)
+            });
+        };
+
+        match param(url, "sort").as_deref() {
+            None | Some("NEWEST") => {}
+            Some("PRICE_ASC") => orders.sort_by_key(|o| U256::from_dec_str(&o.price).unwrap_or_default()),
+            Some("PRICE_DESC") => orders.sort_by_key(|o| std::cmp::Reverse(U256::from_dec_str(&o.price).unwrap_or_default())),
+            Some("EXPIRING_SOON") => orders.sort_by_key(|o| o.end_time),
+            Some(sort) => return Err(format!("Invalid sort: {}", sort)),
+        };
+
+        if let Some(cursor) = param(url, "pagination[cursor]") {
+            let position = orders
+                .iter()
+                .position(|o| o.hash == cursor)
+                .ok_or_else(|| format!("Unknown cursor: {}", cursor))?;
+            orders.drain(..=position);
+        };
+
+        let first = match param(url, "pagination[first]") {
+            Some(first) => first.parse::<usize>().map_err(|e| e.to_string())?.min(MAX_PAGE_SIZE),
+            None => DEFAULT_PAGE_SIZE,
+        };
+        orders.truncate(first);
+
+        Ok(orders.into_iter().cloned().collect())
+    }
+
+    fn create_order(&mut self, req: CreateOrderRequest) -> Result<Order, String> {
+        let nonce: u64 = req.nonce.parse().map_err(|_| format!("Invalid nonce: {}", req.nonce))?;
+        if req.end_time <= req.start_time {
+            return Err(String::from("End time must be after start time"));
+        }
+        if self.orders.iter().any(|o| o.signer == req.signer && nonce_of(o) == Some(nonce)) {
+            return Err(format!("Nonce already used: {}", nonce));
+        }
+
+        let signature: Option<Signature> = req.signature.parse().ok();
+        let hash = MakerOrder::try_from(&req).map_err(|e| e.to_string())?.hash();
+
+        let order = Order {
+            hash: format!("{:?}", hash),
+            collection_address: req.collection,
+            token_id: req.token_id,
+            is_order_ask: req.is_order_ask,
+            signer: req.signer,
+            strategy: req.strategy,
+            currency_address: req.currency,
+            amount: req.amount,
+            price: req.price,
+            nonce: req.nonce,
+            start_time: req.start_time,
+            end_time: req.end_time,
+            min_percentage_to_ask: req.min_percentage_to_ask,
+            params: req.params,
+            status: String::from("VALID"),
+            v: signature.map(|s| s.v as u8),
+            r: signature.map(|s| H256::from_uint(&s.r)),
+            s: signature.map(|s| H256::from_uint(&s.s)),
+            signature: Some(req.signature),
+        };
+
+        self.orders.push(order.clone());
+
+        Ok(order)
+    }
+}
+
+struct Router {
+    state: Arc<Mutex<State>>,
+}
+
+impl Respond for Router {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let prefix = format!("{}{}", constants::API_PATH, constants::VERSION);
+        let route = match request.url.path().strip_prefix(&prefix) {
+            Some(route) => route,
+            None => return not_found("Unknown route"),
+        };
+        let url = &request.url;
+        let mut state = self.state.lock().unwrap();
+
+        match (&request.method, route) {
+            (Method::Get, "/accounts") => match param_address(url, "address") {
+                Ok(Some(address)) => found(state.accounts.get(&address)),
+                _ => bad_request("Invalid address"),
+            },
+            (Method::Get, "/collections") => match param_address(url, "address") {
+                Ok(Some(address)) => found(state.collections.get(&address)),
+                _ => bad_request("Invalid address"),
+            },
+            (Method::Get, "/collections/stats") => match param_address(url, "address") {
+                Ok(Some(address)) => found(state.collection_stats.get(&address)),
+                _ => bad_request("Invalid address"),
+            },
+            (Method::Get, "/orders/nonce") => match param_address(url, "address") {
+                Ok(Some(address)) => ok(state.next_nonce(address).to_string()),
+                _ => bad_request("Invalid address"),
+            },
+            (Method::Get, "/orders") => match state.query_orders(url) {
+                Ok(orders) => ok(orders),
+                Err(message) => bad_request(&message),
+            },
+            (Method::Post, "/orders") => {
+                let created = serde_json::from_slice::<CreateOrderRequest>(&request.body)
+                    .map_err(|e| e.to_string())
+                    .and_then(|req| state.create_order(req));
+                match created {
+                    Ok(order) => ok(order),
+                    Err(message) => bad_request(&message),
+                }
+            }
+            _ => not_found("Unknown route"),
+        }
+    }
+}
+
+fn nonce_of(order: &Order) -> Option<u64> {
+    order.nonce.parse().ok()
+}
+
+fn param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs().find(|(k, _)| k == key).map(|(_, v)| v.into_owned())
+}
+
+fn params(url: &Url, key: &str) -> Vec<String> {
+    url.query_pairs().filter(|(k, _)| k == key).map(|(_, v)| v.into_owned()).collect()
+}
+
+fn param_address(url: &Url, key: &str) -> Result<Option<Address>, String> {
+    param(url, key)
+        .map(|a| a.parse::<Address>().map_err(|_| format!("Invalid address: {}", a)))
+        .transpose()
+}
+
+fn parse_u256(value: &str) -> Result<U256, String> {
+    U256::from_dec_str(value).map_err(|_| format!("Invalid number: {}", value))
+}
+
+#[derive(Serialize)]
+struct Envelope<T> {
+    success: bool,
+    message: Option<String>,
+    data: Option<T>,
+}
+
+fn ok<T: Serialize>(data: T) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_json(Envelope { success: true, message: None, data: Some(data) })
+}
+
+fn found<T: Serialize>(data: Option<&T>) -> ResponseTemplate {
+    match data {
+        Some(data) => ok(data),
+        None => not_found("Not found"),
+    }
+}
+
+fn not_found(message: &str) -> ResponseTemplate {
+    error(404, message)
+}
+
+fn bad_request(message: &str) -> ResponseTemplate {
+    error(400, message)
+}
+
+fn error(status: u16, message: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_json(Envelope::<()> {
+        success: false,
+        message: Some(message.to_string()),
+        data: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{LooksRareApiError, OrdersRequest, Pagination, Sort, Status};
+
+    fn maker_order(signer: Address, nonce: u64, price: u64) -> CreateOrderRequest {
+        CreateOrderRequest {
+            signature: format!("0x{}1b", "11".repeat(64)),
+            collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+            token_id: nonce.to_string(),
+            signer,
+            strategy: "0x579af6fd30bf83a5ac0d636bc619f98dbdeb930c".parse().unwrap(),
+            currency: "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2".parse().unwrap(),
+            is_order_ask: true,
+            nonce: nonce.to_string(),
+            amount: String::from("1"),
+            price: price.to_string(),
+            start_time: 1667747434,
+            end_time: 1667754634,
+            min_percentage_to_ask: 8500,
+            params: String::new(),
+        }
+    }
+
+    fn signer_orders(signer: Address, sort: Sort) -> OrdersRequest {
+        OrdersRequest {
+            is_order_ask: None,
+            collection: None,
+            token_id: None,
+            signer: Some(signer),
+            nonce: None,
+            strategy: None,
+            currency: None,
+            price: None,
+            start_time: None,
+            end_time: None,
+            status: Some(vec![Status::Valid]),
+            pagination: None,
+            sort: Some(sort),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_query_and_cancel_orders() {
+        let fake = FakeLooksRare::start().await;
+        let api = fake.api();
+        let signer: Address = "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap();
+
+        assert_eq!(api.get_nonce(signer).await.unwrap(), 0);
+
+        for (nonce, price) in [(0, 300), (1, 100), (2, 200)] {
+            api.create_order(maker_order(signer, nonce, price)).await.unwrap();
+        }
+
+        assert_eq!(api.get_nonce(signer).await.unwrap(), 3);
+
+        let orders = api.get_orders(signer_orders(signer, Sort::PriceAsc)).await.unwrap();
+        let prices: Vec<&str> = orders.iter().map(|o| o.price.as_str()).collect();
+        assert_eq!(prices, vec!["100", "200", "300"]);
+
+        assert_eq!(fake.cancel_orders(signer, &[1]), 1);
+
+        let orders = api.get_orders(signer_orders(signer, Sort::Newest)).await.unwrap();
+        let nonces: Vec<&str> = orders.iter().map(|o| o.nonce.as_str()).collect();
+        assert_eq!(nonces, vec!["2", "0"]);
+
+        assert_eq!(fake.cancel_all_orders(signer, 3), 2);
+        assert!(api.get_orders(signer_orders(signer, Sort::Newest)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_reused_nonce() {
+        let fake = FakeLooksRare::start().await;
+        let api = fake.api();
+        let signer: Address = "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap();
+
+        api.create_order(maker_order(signer, 7, 100)).await.unwrap();
+        let err = api.create_order(maker_order(signer, 7, 100)).await.unwrap_err();
+
+        assert!(matches!(err, LooksRareApiError::OrderRejected { .. }));
+    }
+
+    #[tokio::test]
+    async fn paginates_with_cursor() {
+        let fake = FakeLooksRare::start().await;
+        let api = fake.api();
+        let signer: Address = "0x9E69b59b8d2A094CB1117f92Ff7DCf51Ed467B41".parse().unwrap();
+
+        for nonce in 0..5 {
+            api.create_order(maker_order(signer, nonce, 100)).await.unwrap();
+        }
+
+        let mut req = signer_orders(signer, Sort::Newest);
+        req.pagination = Some(Pagination { first: Some(3), cursor: None });
+        let page = api.get_orders(req.clone()).await.unwrap();
+        assert_eq!(page.len(), 3);
+
+        req.pagination = Some(Pagination { first: Some(3), cursor: Some(page[2].hash.clone()) });
+        let rest = api.get_orders(req).await.unwrap();
+        let nonces: Vec<&str> = rest.iter().map(|o| o.nonce.as_str()).collect();
+        assert_eq!(nonces, vec!["1", "0"]);
+    }
+
+    #[tokio::test]
+    async fn serves_accounts_and_collections() {
+        let fake = FakeLooksRare::start().await;
+        let api = fake.api();
+        let address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+
+        assert!(matches!(api.get_account(address).await, Err(LooksRareApiError::AccountNotFound { .. })));
+
+        fake.insert_account(Account {
+            address,
+            name: Some(String::from("looksrare-rs")),
+            biography: None,
+            website_link: None,
+            instagram_link: None,
+            twitter_link: None,
+            is_verified: false,
+        });
+
+        assert_eq!(api.get_account(address).await.unwrap().address, address);
+        assert!(matches!(api.get_collection_information(address).await, Err(LooksRareApiError::CollectionNotFound { .. })));
+    }
+}
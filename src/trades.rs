@@ -0,0 +1,340 @@
+//! Trades and cancellations read straight from `LooksRareExchange` logs,
+//! for indexing sales from a node instead of the API.
+
+use crate::contracts::{
+    CancelAllOrdersFilter, CancelMultipleOrdersFilter, LooksRareExchangeEvents, RoyaltyPaymentFilter, TakerAskFilter,
+    TakerBidFilter,
+};
+use crate::types::Order;
+use ethers::{
+    abi::RawLog,
+    contract::{EthEvent, EthLogDecode},
+    prelude::{Address, Middleware},
+    types::{BlockNumber, Filter, Log, H256, U256},
+};
+use thiserror::Error;
+
+/// A decoded exchange log with its position on chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExchangeLog {
+    pub block_number: Option<u64>,
+    pub transaction_hash: Option<H256>,
+    pub log_index: Option<U256>,
+    pub event: ExchangeEvent,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExchangeEvent {
+    /// A maker bid filled by a seller.
+    TakerAsk(Trade),
+    /// A maker ask filled by a buyer.
+    TakerBid(Trade),
+    CancelMultipleOrders(CancelMultipleOrders),
+    CancelAllOrders(CancelAllOrders),
+    RoyaltyPayment(RoyaltyPayment),
+}
+
+impl ExchangeEvent {
+    /// Hash of the maker order a trade or royalty payment belongs to.
+    pub fn order_hash(&self) -> Option<&str> {
+        match self {
+            ExchangeEvent::TakerAsk(trade) | ExchangeEvent::TakerBid(trade) => Some(&trade.order_hash),
+            ExchangeEvent::RoyaltyPayment(payment) => payment.order_hash.as_deref(),
+            ExchangeEvent::CancelMultipleOrders(_) | ExchangeEvent::CancelAllOrders(_) => None,
+        }
+    }
+}
+
+/// A filled maker order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trade {
+    /// Same format as [`Order::hash`].
+    pub order_hash: String,
+    pub order_nonce: U256,
+    pub taker: Address,
+    pub maker: Address,
+    pub strategy: Address,
+    pub currency: Address,
+    pub collection: Address,
+    pub token_id: U256,
+    pub amount: U256,
+    pub price: U256,
+}
+
+impl Trade {
+    pub fn fills(&self, order: &Order) -> bool {
+        self.order_hash.eq_ignore_ascii_case(&order.hash)
+    }
+}
+
+impl From<TakerAskFilter> for Trade {
+    fn from(log: TakerAskFilter) -> Self {
+        Self {
+            order_hash: format!("{:?}", H256::from(log.order_hash)),
+            order_nonce: log.order_nonce,
+            taker: log.taker,
+            maker: log.maker,
+            strategy: log.strategy,
+            currency: log.currency,
+            collection: log.collection,
+            token_id: log.token_id,
+            amount: log.amount,
+            price: log.price,
+        }
+    }
+}
+
+impl From<TakerBidFilter> for Trade {
+    fn from(log: TakerBidFilter) -> Self {
+        Self {
+            order_hash: format!("{:?}", H256::from(log.order_hash)),
+            order_nonce: log.order_nonce,
+            taker: log.taker,
+            maker: log.maker,
+            strategy: log.strategy,
+            currency: log.currency,
+            collection: log.collection,
+            token_id: log.token_id,
+            amount: log.amount,
+            price: log.price,
+        }
+    }
+}
+
+/// Maker orders of `user` cancelled by nonce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancelMultipleOrders {
+    pub user: Address,
+    pub order_nonces: Vec<U256>,
+}
+
+impl CancelMultipleOrders {
+    pub fn cancels(&self, order: &Order) -> bool {
+        order.signer == self.user
+            && matches!(U256::from_dec_str(&order.nonce), Ok(nonce) if self.order_nonces.contains(&nonce))
+    }
+}
+
+/// Every maker order of `user` with a nonce below `new_min_nonce` cancelled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CancelAllOrders {
+    pub user: Address,
+    pub new_min_nonce: U256,
+}
+
+impl CancelAllOrders {
+    pub fn cancels(&self, order: &Order) -> bool {
+        order.signer == self.user && matches!(U256::from_dec_str(&order.nonce), Ok(nonce) if nonce < self.new_min_nonce)
+    }
+}
+
+/// Royalty paid out of a trade's price.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RoyaltyPayment {
+    pub collection: Address,
+    pub token_id: U256,
+    pub royalty_recipient: Address,
+    pub currency: Address,
+    pub amount: U256,
+    /// The royalty log does not carry the order; this is taken from the
+    /// trade of the same token following it in the same transaction, when
+    /// decoded through [`decode_logs`].
+    pub order_hash: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum TradesError<M: Middleware> {
+    #[error(transparent)]
+    MiddlewareError(M::Error),
+    #[error(transparent)]
+    DecodeError(#[from] ethers::abi::Error),
+}
+
+/// Decodes a single exchange log. Fails on logs of other events or contracts.
+pub fn decode_log(log: Log) -> Result<ExchangeLog, ethers::abi::Error> {
+    let block_number = log.block_number.map(|number| number.as_u64());
+    let transaction_hash = log.transaction_hash;
+    let log_index = log.log_index;
+
+    let event = match LooksRareExchangeEvents::decode_log(&RawLog::from(log))? {
+        LooksRareExchangeEvents::TakerAskFilter(log) => ExchangeEvent::TakerAsk(log.into()),
+        LooksRareExchangeEvents::TakerBidFilter(log) => ExchangeEvent::TakerBid(log.into()),
+        LooksRareExchangeEvents::CancelMultipleOrdersFilter(log) => {
+            ExchangeEvent::CancelMultipleOrders(CancelMultipleOrders {
+                user: log.user,
+                order_nonces: log.order_nonces,
+            })
+        }
+        LooksRareExchangeEvents::CancelAllOrdersFilter(log) => ExchangeEvent::CancelAllOrders(CancelAllOrders {
+            user: log.user,
+            new_min_nonce: log.new_min_nonce,
+        }),
+        LooksRareExchangeEvents::RoyaltyPaymentFilter(log) => ExchangeEvent::RoyaltyPayment(RoyaltyPayment {
+            collection: log.collection,
+            token_id: log.token_id,
+            royalty_recipient: log.royalty_recipient,
+            currency: log.currency,
+            amount: log.amount,
+            order_hash: None,
+        }),
+    };
+
+    Ok(ExchangeLog {
+        block_number,
+        transaction_hash,
+        log_index,
+        event,
+    })
+}
+
+/// Decodes exchange logs, given in chain order, and links royalty payments
+/// to the trades they were paid from.
+pub fn decode_logs(logs: Vec<Log>) -> Result<Vec<ExchangeLog>, ethers::abi::Error> {
+    let mut decoded = logs.into_iter().map(decode_log).collect::<Result<Vec<_>, _>>()?;
+
+    for i in 0..decoded.len() {
+        let (collection, token_id) = match &decoded[i].event {
+            ExchangeEvent::RoyaltyPayment(payment) => (payment.collection, payment.token_id),
+            _ => continue,
+        };
+        let transaction_hash = decoded[i].transaction_hash;
+
+        let order_hash = decoded[i + 1..]
+            .iter()
+            .take_while(|later| later.transaction_hash == transaction_hash)
+            .find_map(|later| match &later.event {
+                ExchangeEvent::TakerAsk(trade) | ExchangeEvent::TakerBid(trade)
+                    if trade.collection == collection && trade.token_id == token_id =>
+                {
+                    Some(trade.order_hash.clone())
+                }
+                _ => None,
+            });
+        if let ExchangeEvent::RoyaltyPayment(payment) = &mut decoded[i].event {
+            payment.order_hash = order_hash;
+        }
+    }
+
+    Ok(decoded)
+}
+
+/// Fetches and decodes the trade, cancellation and royalty logs `exchange`
+/// emitted between `from_block` and `to_block`, both inclusive.
+///
+/// Many providers cap the block range of a single `eth_getLogs`; split long
+/// ranges into several calls.
+pub async fn fetch_exchange_logs<M: Middleware>(
+    client: &M,
+    exchange: Address,
+    from_block: impl Into<BlockNumber>,
+    to_block: impl Into<BlockNumber>,
+) -> Result<Vec<ExchangeLog>, TradesError<M>> {
+    let filter = Filter::new()
+        .address(exchange)
+        .from_block(from_block)
+        .to_block(to_block)
+        .topic0(vec![
+            TakerAskFilter::signature(),
+            TakerBidFilter::signature(),
+            CancelMultipleOrdersFilter::signature(),
+            CancelAllOrdersFilter::signature(),
+            RoyaltyPaymentFilter::signature(),
+        ]);
+    let logs = client.get_logs(&filter).await.map_err(TradesError::MiddlewareError)?;
+    Ok(decode_logs(logs)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture;
+    use ethers::{
+        abi::{encode, Token},
+        providers::Provider,
+        types::{Bytes, U64},
+    };
+
+    const COLLECTION: &str = "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258";
+
+    fn log(topics: Vec<H256>, data: Vec<Token>, log_index: u64) -> Log {
+        Log {
+            address: Address::repeat_byte(0x59),
+            topics,
+            data: Bytes::from(encode(&data)),
+            block_number: Some(U64::from(15_000_000)),
+            transaction_hash: Some(H256::repeat_byte(0x77)),
+            log_index: Some(U256::from(log_index)),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_trades_and_links_royalties() {
+        let collection: Address = COLLECTION.parse().unwrap();
+        let buyer = Address::repeat_byte(0xb0);
+        let seller: Address = "0x1A92f7381B9F03921564a437210bB9396471050C".parse().unwrap();
+        let weth = Address::repeat_byte(0xee);
+        let order_hash = H256::from_low_u64_be(0xa0);
+
+        let royalty = log(
+            vec![
+                RoyaltyPaymentFilter::signature(),
+                H256::from(collection),
+                H256::from_low_u64_be(1),
+                H256::from(Address::repeat_byte(0x99)),
+            ],
+            vec![Token::Address(weth), Token::Uint(U256::exp10(16))],
+            0,
+        );
+        let trade = log(
+            vec![
+                TakerBidFilter::signature(),
+                H256::from(buyer),
+                H256::from(seller),
+                H256::from(Address::repeat_byte(0x56)),
+            ],
+            vec![
+                Token::FixedBytes(order_hash.as_bytes().to_vec()),
+                Token::Uint(U256::from(100)),
+                Token::Address(weth),
+                Token::Address(collection),
+                Token::Uint(U256::one()),
+                Token::Uint(U256::one()),
+                Token::Uint(U256::exp10(18)),
+            ],
+            1,
+        );
+        let cancel = log(
+            vec![CancelAllOrdersFilter::signature(), H256::from(seller)],
+            vec![Token::Uint(U256::from(101))],
+            2,
+        );
+
+        let (provider, mock) = Provider::mocked();
+        mock.push::<Vec<Log>, _>(vec![royalty, trade, cancel]).unwrap();
+        let logs = fetch_exchange_logs(&provider, Address::repeat_byte(0x59), 15_000_000u64, 15_000_010u64)
+            .await
+            .unwrap();
+
+        let orders: Vec<Order> = serde_json::from_value(fixture("v1/orders_page")["data"].clone()).unwrap();
+        let (filled, open) = (&orders[0], &orders[1]);
+
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0].event.order_hash(), Some(filled.hash.as_str()));
+        match &logs[1].event {
+            ExchangeEvent::TakerBid(trade) => {
+                assert!(trade.fills(filled));
+                assert_eq!(trade.taker, buyer);
+                assert_eq!(trade.price, U256::exp10(18));
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+        match &logs[2].event {
+            ExchangeEvent::CancelAllOrders(cancel) => {
+                assert!(cancel.cancels(filled));
+                assert!(!cancel.cancels(open));
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE},
+    Client,
+    Method,
+    StatusCode,
+    Url,
+};
+use serde::Serialize;
+
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Sends the HTTP requests made by [`LooksRareApi`](crate::api::LooksRareApi).
+///
+/// Implement this to route requests through your own stack, e.g. to trace
+/// them, sign them for a proxy, or replay recorded responses in tests.
+/// Non-2xx responses are not errors at this level; the client inspects the
+/// status itself.
+///
+/// On `wasm32` the trait drops its `Send` requirement on futures, since
+/// browser fetches are not `Send`.
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Transport: Send + Sync {
+    async fn send(&self, req: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    /// Full URL, query string included.
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    pub fn get(url: Url) -> Self {
+        Self {
+            method: Method::GET,
+            url,
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    pub fn post_json(url: Url, body: &impl Serialize) -> Result<Self, serde_json::Error> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        Ok(Self {
+            method: Method::POST,
+            url,
+            headers,
+            body: Some(serde_json::to_vec(body)?),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<T: Transport + ?Sized> Transport for std::sync::Arc<T> {
+    async fn send(&self, req: HttpRequest) -> Result<HttpResponse, TransportError> {
+        (**self).send(req).await
+    }
+}
+
+/// The default transport, backed by a `reqwest::Client`.
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl Transport for ReqwestTransport {
+    async fn send(&self, req: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let mut builder = self.client.request(req.method, req.url).headers(req.headers);
+        if let Some(body) = req.body {
+            builder = builder.body(body);
+        }
+
+        let res = builder.send().await?;
+
+        Ok(HttpResponse {
+            status: res.status(),
+            headers: res.headers().clone(),
+            body: res.text().await?,
+        })
+    }
+}
@@ -1,26 +1,30 @@
 use crate::constants;
 use serde::{Deserialize, Serialize};
-use ethers::{
-    prelude::Address, 
-    types::H256,
-};
+use ethers_core::types::{Address, Bytes, H256, U256};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Network {
     Mainnet,
+    /// Any other deployment of the API, e.g. a local mock server.
+    Custom(String),
 }
 
 impl Network {
     pub fn url(&self) -> &str {
         match self {
             Network::Mainnet => constants::API_BASE_MAINNET,
+            Network::Custom(url) => url.trim_end_matches('/'),
         }
     }
 
     pub fn api(&self) -> String {
+        self.api_version(constants::VERSION)
+    }
+
+    pub fn api_version(&self, version: u64) -> String {
         let url = self.url();
-        format!("{}{}{}", url, constants::API_PATH, constants::VERSION)
+        format!("{}{}{}", url, constants::API_PATH, version)
     }
 }
 
@@ -60,6 +64,36 @@ pub struct Order {
     pub s: Option<H256>,
 }
 
+impl Order {
+    /// Price per item; `None` if the price or amount do not parse or the
+    /// amount is zero.
+    pub fn unit_price(&self) -> Option<U256> {
+        let price = U256::from_dec_str(&self.price).ok()?;
+        let amount = self.amount.parse::<u64>().ok().filter(|amount| *amount > 0)?;
+        Some(price / amount)
+    }
+}
+
+/// A v1 maker order as signed for `LooksRareExchange`.
+///
+/// `min_percentage_to_ask` is in basis points of the price.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MakerOrder {
+    pub is_order_ask: bool,
+    pub signer: Address,
+    pub collection: Address,
+    pub price: U256,
+    pub token_id: U256,
+    pub amount: U256,
+    pub strategy: Address,
+    pub currency: Address,
+    pub nonce: U256,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub min_percentage_to_ask: u64,
+    pub params: Bytes,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CollectionInformation {
@@ -133,4 +167,132 @@ pub struct CollectionRewards {
     pub volume_24h_global: String,
     pub points: u64,
     pub floor_global: String,
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRewards {
+    pub address: Address,
+    pub trading: Rewards,
+    pub listing: Rewards,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rewards {
+    #[serde(with = "u256_dec")]
+    pub pending: U256,
+    #[serde(with = "u256_dec")]
+    pub claimed: U256,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionRewardPoints {
+    pub collection: Address,
+    #[serde(with = "u256_dec")]
+    pub points: U256,
+    #[serde(with = "u256_dec")]
+    pub rewards: U256,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsEpoch {
+    pub epoch: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    #[serde(with = "u256_dec")]
+    pub trading_rewards: U256,
+    #[serde(with = "u256_dec")]
+    pub listing_rewards: U256,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsProof {
+    pub address: Address,
+    #[serde(with = "u256_dec")]
+    pub amount: U256,
+    pub merkle_root: H256,
+    pub proof: Vec<H256>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventType {
+    Mint,
+    Transfer,
+    List,
+    Sale,
+    Offer,
+    CancelList,
+    CancelOffer,
+}
+
+impl EventType {
+    pub fn to_str(&self) -> &str {
+        match &self {
+            EventType::Mint => "MINT",
+            EventType::Transfer => "TRANSFER",
+            EventType::List => "LIST",
+            EventType::Sale => "SALE",
+            EventType::Offer => "OFFER",
+            EventType::CancelList => "CANCEL_LIST",
+            EventType::CancelOffer => "CANCEL_OFFER",
+        }
+    }
+}
+
+/// A marketplace or token event, newest first in API responses.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub id: u64,
+    pub from: Address,
+    pub to: Option<Address>,
+    #[serde(rename = "type")]
+    pub type_: EventType,
+    /// Transaction hash, for events that happened on chain.
+    pub hash: Option<String>,
+    pub created_at: String,
+    /// The order listed, offered, sold or cancelled.
+    pub order: Option<Order>,
+}
+
+/// The API returns token amounts as base-10 strings, whereas `U256`'s own
+/// serde impl expects `0x`-prefixed hex.
+pub(crate) mod u256_dec {
+    use ethers_core::types::U256;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        U256::from_dec_str(&s).map_err(D::Error::custom)
+    }
+}
+
+pub(crate) mod u256_dec_vec {
+    use ethers_core::types::U256;
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[U256], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_string())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<U256>, D::Error> {
+        let strings = Vec::<String>::deserialize(deserializer)?;
+        strings
+            .iter()
+            .map(|s| U256::from_dec_str(s).map_err(D::Error::custom))
+            .collect()
+    }
+}
@@ -0,0 +1,337 @@
+use crate::api::{
+    address_query, record_outcome, request_span, request_url, send, LooksRareApiError, Pagination, Query, Sort, Status,
+};
+use crate::constants;
+use crate::rate_limit::RateLimiter;
+#[cfg(feature = "metrics")]
+use crate::telemetry;
+use crate::transport::{HttpRequest, ReqwestTransport, Transport};
+use crate::types::Network;
+use crate::v2::types::{Maker, MerkleTree, Order, QuoteType, UserNonces};
+use ethers_core::types::{Address, Bytes, U256};
+use instant::Instant;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::Instrument;
+
+/// Client for the v2 (`LooksRareProtocol`) API.
+///
+/// Made from a v1 client with [`v2`](crate::api::LooksRareApi::v2), it shares
+/// that client's rate limiter, so mixed v1 and v2 calls stay within one budget.
+pub struct LooksRareApi {
+    transport: Arc<dyn Transport>,
+    network: Network,
+    rate_limiter: Option<Arc<RateLimiter>>,
+}
+
+impl LooksRareApi {
+    pub fn new() -> Self {
+        Self::from_parts(Arc::new(ReqwestTransport::default()), Network::Mainnet, None)
+    }
+
+    pub(crate) fn from_parts(
+        transport: Arc<dyn Transport>,
+        network: Network,
+        rate_limiter: Option<Arc<RateLimiter>>,
+    ) -> Self {
+        Self {
+            transport,
+            network,
+            rate_limiter,
+        }
+    }
+
+    pub async fn get_orders(&self, req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders", api), &orders_query(req))?;
+        self.send(HttpRequest::get(url), parse_orders).await
+    }
+
+    pub async fn get_nonces(&self, address: Address) -> Result<UserNonces, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders/nonce", api), &address_query(address)?)?;
+        self.send(HttpRequest::get(url), |text| parse_nonces(text, address)).await
+    }
+
+    pub async fn create_order(&self, req: CreateOrderRequest) -> Result<Order, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders", api), &[])?;
+        self.send(HttpRequest::post_json(url, &req)?, parse_created_order).await
+    }
+
+    async fn send<T>(
+        &self,
+        request: HttpRequest,
+        parse: impl FnOnce(&str) -> Result<T, LooksRareApiError>,
+    ) -> Result<T, LooksRareApiError> {
+        #[cfg(feature = "metrics")]
+        let url = request.url.to_string();
+        let span = request_span(&request);
+        let start = Instant::now();
+
+        let mut status = None;
+        let result = send(self.transport.as_ref(), self.rate_limiter.as_deref(), request)
+            .instrument(span.clone())
+            .await
+            .and_then(|res| {
+                status = Some(res.status);
+                parse(&res.body)
+            });
+        record_outcome(&span, start.elapsed(), status, &result);
+
+        #[cfg(feature = "metrics")]
+        if let Err(e) = &result {
+            telemetry::record_error(&url, e);
+        }
+
+        result
+    }
+}
+
+impl Default for LooksRareApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Request building and response parsing, shared with `crate::v2::blocking`.
+
+pub(crate) fn orders_query(req: OrdersRequest) -> Query {
+    let mut query = vec![];
+
+    if let Some(quote_type) = req.quote_type { query.push(("quoteType", u8::from(quote_type).to_string())); };
+    if let Some(collection) = req.collection { query.push(("collection", format!("{:?}", collection))); };
+    if let Some(item_id) = req.item_id { query.push(("itemId", item_id.to_string())); };
+    if let Some(signer) = req.signer { query.push(("signer", format!("{:?}", signer))); };
+    if let Some(strategy_id) = req.strategy_id { query.push(("strategyId", strategy_id.to_string())); };
+    if let Some(currency) = req.currency { query.push(("currency", format!("{:?}", currency))); };
+    if let Some(status) = &req.status { status.iter().for_each(|x| query.push(("status", x.to_str().to_string()))); };
+    if let Some(pagination) = &req.pagination {
+        if let Some(first) = pagination.first { query.push(("pagination[first]", first.to_string())); };
+        if let Some(cursor) = &pagination.cursor { query.push(("pagination[cursor]", cursor.clone())); };
+    };
+    if let Some(sort) = &req.sort { query.push(("sort", sort.to_str().to_string())); };
+
+    query.into_iter().map(|(k, v)| (k, v.into())).collect()
+}
+
+pub(crate) fn parse_orders(text: &str) -> Result<Vec<Order>, LooksRareApiError> {
+    let resp: OrdersResponse = serde_json::from_str(text)?;
+    let data: Vec<Order> = resp.data.ok_or(LooksRareApiError::OrdersNotFound)?;
+
+    Ok(data)
+}
+
+pub(crate) fn parse_nonces(text: &str, address: Address) -> Result<UserNonces, LooksRareApiError> {
+    let resp: NoncesResponse = serde_json::from_str(text)?;
+    let nonces: UserNonces = resp.data.ok_or(LooksRareApiError::NonceNotFound {
+        address
+    })?;
+
+    Ok(nonces)
+}
+
+pub(crate) fn parse_created_order(text: &str) -> Result<Order, LooksRareApiError> {
+    let resp: CreateOrderResponse = serde_json::from_str(text)?;
+    let order: Order = resp.data.ok_or(LooksRareApiError::OrderRejected {
+        message: resp.message.unwrap_or_default(),
+    })?;
+
+    Ok(order)
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OrdersRequest {
+    pub quote_type: Option<QuoteType>,
+    pub collection: Option<Address>,
+    pub item_id: Option<U256>,
+    pub signer: Option<Address>,
+    pub strategy_id: Option<u64>,
+    pub currency: Option<Address>,
+    pub status: Option<Vec<Status>>,
+    pub pagination: Option<Pagination>,
+    pub sort: Option<Sort>,
+}
+
+/// A signed maker order as submitted to `POST /orders`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderRequest {
+    #[serde(flatten)]
+    pub maker: Maker,
+    pub signature: Bytes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merkle_tree: Option<MerkleTree>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct OrdersResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<Vec<Order>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct NoncesResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<UserNonces>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CreateOrderResponse {
+    success: bool,
+    message: Option<String>,
+    data: Option<Order>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{api, fixture, mock_get};
+    use crate::v2::types::Maker;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock,
+        MockServer,
+        ResponseTemplate,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_orders() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v2/orders", &[
+            ("quoteType", "1"),
+            ("collection", "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258"),
+            ("status", "VALID"),
+            ("pagination[first]", "4"),
+            ("sort", "NEWEST"),
+        ], "v2/orders").await;
+        let api = api(&server).v2();
+
+        let req = OrdersRequest {
+            quote_type: Some(QuoteType::Ask),
+            collection: Some("0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap()),
+            status: Some(vec![Status::Valid]),
+            pagination: Some(Pagination {
+                first: Some(4),
+                cursor: None,
+            }),
+            sort: Some(Sort::Newest),
+            ..Default::default()
+        };
+
+        let orders: Vec<Order> = api.get_orders(req).await.unwrap();
+
+        assert_eq!(orders.len(), 4);
+        assert_eq!(orders[1].item_ids, vec![U256::from(62963)]);
+        assert_eq!(orders[1].price, U256::exp10(18) * 2);
+    }
+
+    #[tokio::test]
+    async fn shares_the_rate_limiter_of_the_v1_client() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/orders/nonce"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        mock_get(&server, "/api/v2/orders/nonce", &[], "v2/orders_nonce").await;
+        let api = crate::api::LooksRareApi::builder()
+            .base_url(server.uri())
+            .rate_limiter(RateLimiter::per_second(50))
+            .build()
+            .v2();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonces: UserNonces = api.get_nonces(input_address).await.unwrap();
+
+        assert_eq!(nonces.bid_nonce, U256::from(5));
+    }
+
+    #[tokio::test]
+    async fn can_get_nonces() {
+        let server = MockServer::start().await;
+        mock_get(&server, "/api/v2/orders/nonce", &[("address", "0x3d67b76cf3dcc881255eb2262e788be03b2f5b9f")], "v2/orders_nonce").await;
+        let api = api(&server).v2();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+        let nonces: UserNonces = api.get_nonces(input_address).await.unwrap();
+
+        assert_eq!(nonces.ask_nonce, U256::from(2));
+        assert_eq!(nonces.bid_nonce, U256::from(5));
+    }
+
+    #[tokio::test]
+    async fn can_create_order() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v2/orders"))
+            .and(body_partial_json(serde_json::json!({
+                "quoteType": 1,
+                "orderNonce": "0",
+                "itemIds": ["62962"],
+                "signature": format!("0x{}", "44".repeat(65)),
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture("v2/orders_create")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let api = api(&server).v2();
+
+        let order: Order = serde_json::from_value(fixture("v2/orders_create")["data"].clone()).unwrap();
+        let maker: Maker = order.maker();
+        let req = CreateOrderRequest {
+            maker,
+            signature: order.signature.clone(),
+            merkle_tree: None,
+        };
+
+        let created: Order = api.create_order(req).await.unwrap();
+
+        assert_eq!(created.hash, order.hash);
+    }
+}
+
+#[cfg(all(test, feature = "live-tests"))]
+mod live_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn can_get_orders() {
+        let api = LooksRareApi::new();
+
+        let req = OrdersRequest {
+            quote_type: Some(QuoteType::Ask),
+            collection: Some("0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap()),
+            status: Some(vec![Status::Valid]),
+            pagination: Some(Pagination {
+                first: Some(4),
+                cursor: None,
+            }),
+            sort: Some(Sort::Newest),
+            ..Default::default()
+        };
+
+        let input_collection: Address = req.collection.unwrap();
+
+        let orders: Vec<Order> = api.get_orders(req).await.unwrap();
+
+        assert!(orders.len() <= 4);
+        assert!(orders.iter().all(|o| o.collection == input_collection && o.quote_type == QuoteType::Ask));
+    }
+
+    #[tokio::test]
+    async fn can_get_nonces() {
+        let api = LooksRareApi::new();
+
+        let input_address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+
+        let nonces: UserNonces = api.get_nonces(input_address).await.unwrap();
+
+        assert!(nonces.ask_nonce <= U256::from(u128::MAX) && nonces.bid_nonce <= U256::from(u128::MAX));
+    }
+}
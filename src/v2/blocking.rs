@@ -0,0 +1,54 @@
+//! Synchronous client for the v2 API, mirroring [`crate::v2::api::LooksRareApi`].
+
+use crate::api::{address_query, request_url, LooksRareApiError};
+use crate::blocking::send;
+use crate::constants;
+use crate::transport::HttpRequest;
+use crate::types::Network;
+use crate::v2::api::{orders_query, parse_created_order, parse_nonces, parse_orders, CreateOrderRequest, OrdersRequest};
+use crate::v2::types::{Order, UserNonces};
+use ethers_core::types::Address;
+use reqwest::blocking::Client;
+
+/// Client for the v2 (`LooksRareProtocol`) API.
+pub struct LooksRareApi {
+    client: Client,
+    network: Network,
+}
+
+impl LooksRareApi {
+    pub fn new() -> Self {
+        Self::from_parts(Client::new(), Network::Mainnet)
+    }
+
+    pub(crate) fn from_parts(client: Client, network: Network) -> Self {
+        Self { client, network }
+    }
+
+    pub fn get_orders(&self, req: OrdersRequest) -> Result<Vec<Order>, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders", api), &orders_query(req))?;
+
+        send(&self.client, HttpRequest::get(url), parse_orders)
+    }
+
+    pub fn get_nonces(&self, address: Address) -> Result<UserNonces, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders/nonce", api), &address_query(address)?)?;
+
+        send(&self.client, HttpRequest::get(url), |text| parse_nonces(text, address))
+    }
+
+    pub fn create_order(&self, req: CreateOrderRequest) -> Result<Order, LooksRareApiError> {
+        let api = self.network.api_version(constants::VERSION_V2);
+        let url = request_url(&format!("{}/orders", api), &[])?;
+
+        send(&self.client, HttpRequest::post_json(url, &req)?, parse_created_order)
+    }
+}
+
+impl Default for LooksRareApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,70 @@
+use crate::v2::types;
+use ethers::prelude::abigen;
+
+abigen!(
+    LooksRareProtocol,
+    r#"[
+        struct Taker { address recipient; bytes additionalParameters; }
+        struct Maker { uint8 quoteType; uint256 globalNonce; uint256 subsetNonce; uint256 orderNonce; uint256 strategyId; uint8 collectionType; address collection; address currency; address signer; uint256 startTime; uint256 endTime; uint256 price; uint256[] itemIds; uint256[] amounts; bytes additionalParameters; }
+        struct MerkleTreeNode { bytes32 value; uint8 position; }
+        struct MerkleTree { bytes32 root; MerkleTreeNode[] proof; }
+        function executeTakerAsk(Taker takerAsk, Maker makerBid, bytes makerSignature, MerkleTree merkleTree) external
+        function executeTakerBid(Taker takerBid, Maker makerAsk, bytes makerSignature, MerkleTree merkleTree) external payable
+        function executeMultipleTakerBids(Taker[] takerBids, Maker[] makerAsks, bytes[] makerSignatures, MerkleTree[] merkleTrees, bool isAtomic) external payable
+        function cancelOrderNonces(uint256[] orderNonces) external
+        function cancelSubsetNonces(uint256[] subsetNonces) external
+        function incrementBidAskNonces(bool bid, bool ask) external
+        function userBidAskNonces(address user) external view returns (uint256 bidNonce, uint256 askNonce)
+        function userOrderNonce(address user, uint256 orderNonce) external view returns (bytes32)
+        function userSubsetNonce(address user, uint256 subsetNonce) external view returns (bool)
+        function domainSeparator() external view returns (bytes32)
+        function hashBatchOrder(bytes32 root, uint256 proofLength) external pure returns (bytes32 batchOrderHash)
+    ]"#,
+);
+
+impl From<&types::Maker> for Maker {
+    fn from(maker: &types::Maker) -> Self {
+        Maker {
+            quote_type: maker.quote_type.into(),
+            global_nonce: maker.global_nonce,
+            subset_nonce: maker.subset_nonce,
+            order_nonce: maker.order_nonce,
+            strategy_id: maker.strategy_id.into(),
+            collection_type: maker.collection_type.into(),
+            collection: maker.collection,
+            currency: maker.currency,
+            signer: maker.signer,
+            start_time: maker.start_time.into(),
+            end_time: maker.end_time.into(),
+            price: maker.price,
+            item_ids: maker.item_ids.clone(),
+            amounts: maker.amounts.clone(),
+            additional_parameters: maker.additional_parameters.clone(),
+        }
+    }
+}
+
+impl From<&types::Taker> for Taker {
+    fn from(taker: &types::Taker) -> Self {
+        Taker {
+            recipient: taker.recipient,
+            additional_parameters: taker.additional_parameters.clone(),
+        }
+    }
+}
+
+impl From<&types::MerkleTree> for MerkleTree {
+    fn from(tree: &types::MerkleTree) -> Self {
+        MerkleTree {
+            root: tree.root.0,
+            proof: tree
+                .proof
+                .iter()
+                .map(|node| MerkleTreeNode {
+                    value: node.value.0,
+                    position: node.position.into(),
+                })
+                .collect(),
+        }
+    }
+}
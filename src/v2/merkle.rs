@@ -0,0 +1,293 @@
+use crate::v2::api::CreateOrderRequest;
+use crate::v2::signing::MAKER_TYPE;
+use crate::v2::types::{Maker, MerkleTree, MerkleTreeNode, MerkleTreeNodePosition};
+use ethers_core::{
+    abi::{encode, Token},
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712},
+        Bytes, Signature, H256,
+    },
+    utils::keccak256,
+};
+use ethers_signers::Signer;
+use thiserror::Error;
+
+/// Deepest tree `LooksRareProtocol` accepts (`MAX_CALLDATA_PROOF_LENGTH`).
+pub const MAX_HEIGHT: usize = 10;
+
+/// Many maker orders signed at once through a single merkle root.
+///
+/// The tree mirrors the EIP-712 encoding of `BatchOrder(Maker[2]..[2] tree)`:
+/// leaves are maker order hashes, padded with the hash of an empty maker up to
+/// `2^height`, and each parent is `keccak256(left ++ right)` without sorting.
+#[derive(Clone, Debug)]
+pub struct MerkleOrderBatch {
+    makers: Vec<Maker>,
+    layers: Vec<Vec<H256>>,
+}
+
+impl MerkleOrderBatch {
+    pub fn new(makers: Vec<Maker>) -> Result<Self, MerkleBatchError> {
+        if makers.is_empty() {
+            return Err(MerkleBatchError::Empty);
+        }
+
+        let height = height_for(makers.len());
+        if height > MAX_HEIGHT {
+            return Err(MerkleBatchError::TooManyOrders { count: makers.len() });
+        }
+
+        let padding = Maker::default().hash();
+        let mut leaves: Vec<H256> = makers.iter().map(Maker::hash).collect();
+        leaves.resize(1 << height, padding);
+
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let parents = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], pair[1]))
+                .collect();
+            layers.push(parents);
+        }
+
+        Ok(Self { makers, layers })
+    }
+
+    pub fn makers(&self) -> &[Maker] {
+        &self.makers
+    }
+
+    pub fn height(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn root(&self) -> H256 {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Root and proof for the maker at `index`, or `None` if the batch has
+    /// no maker there.
+    pub fn merkle_tree(&self, index: usize) -> Option<MerkleTree> {
+        if index >= self.makers.len() {
+            return None;
+        }
+
+        let mut position = index;
+        let proof = self.layers[..self.height()]
+            .iter()
+            .map(|layer| {
+                let sibling = position ^ 1;
+                let node = MerkleTreeNode {
+                    value: layer[sibling],
+                    position: if sibling < position {
+                        MerkleTreeNodePosition::Left
+                    } else {
+                        MerkleTreeNodePosition::Right
+                    },
+                };
+                position /= 2;
+                node
+            })
+            .collect();
+
+        Some(MerkleTree { root: self.root(), proof })
+    }
+
+    /// EIP-712 `hashStruct` of the `BatchOrder`.
+    pub fn hash(&self) -> H256 {
+        batch_order_hash(self.root(), self.height())
+    }
+
+    pub fn typed(&self, domain: EIP712Domain) -> TypedBatchOrder {
+        TypedBatchOrder {
+            root: self.root(),
+            height: self.height(),
+            domain,
+        }
+    }
+
+    /// Signs the root once, covering every maker in the batch. Takes any
+    /// ethers [`Signer`], e.g. a [`SigningKey`](crate::keys::SigningKey).
+    pub async fn sign<S: Signer>(
+        self,
+        signer: &S,
+        domain: EIP712Domain,
+    ) -> Result<SignedMerkleOrderBatch, S::Error> {
+        let signature = signer.sign_typed_data(&self.typed(domain)).await?;
+
+        Ok(SignedMerkleOrderBatch { batch: self, signature })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SignedMerkleOrderBatch {
+    pub batch: MerkleOrderBatch,
+    pub signature: Signature,
+}
+
+impl SignedMerkleOrderBatch {
+    /// One request per maker, each carrying the shared signature and its own proof.
+    pub fn orders(&self) -> Vec<CreateOrderRequest> {
+        let signature = Bytes::from(self.signature.to_vec());
+
+        self.batch
+            .makers
+            .iter()
+            .enumerate()
+            .map(|(index, maker)| CreateOrderRequest {
+                maker: maker.clone(),
+                signature: signature.clone(),
+                merkle_tree: self.batch.merkle_tree(index),
+            })
+            .collect()
+    }
+}
+
+/// The batch root bound to the domain it is signed for.
+#[derive(Clone, Debug)]
+pub struct TypedBatchOrder {
+    pub root: H256,
+    pub height: usize,
+    pub domain: EIP712Domain,
+}
+
+impl Eip712 for TypedBatchOrder {
+    type Error = MerkleBatchError;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(self.domain.clone())
+    }
+
+    /// The `BatchOrder` type depends on the tree height; use
+    /// [`batch_order_type_hash`] instead.
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Err(MerkleBatchError::HeightRequired)
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(batch_order_hash(self.root, self.height).0)
+    }
+}
+
+/// `keccak256("BatchOrder(Maker[2]..[2] tree)Maker(...)")` with `height` dimensions.
+pub fn batch_order_type_hash(height: usize) -> H256 {
+    let batch_type = format!("BatchOrder(Maker{} tree){}", "[2]".repeat(height), MAKER_TYPE);
+    H256::from(keccak256(batch_type))
+}
+
+/// Mirrors `LooksRareProtocol.hashBatchOrder(root, proofLength)`.
+pub fn batch_order_hash(root: H256, height: usize) -> H256 {
+    H256::from(keccak256(encode(&[
+        Token::FixedBytes(batch_order_type_hash(height).as_bytes().to_vec()),
+        Token::FixedBytes(root.as_bytes().to_vec()),
+    ])))
+}
+
+/// Verifies `leaf` against `tree` the way `MerkleProofCalldataWithNodes` does.
+pub fn verify_proof(tree: &MerkleTree, leaf: H256) -> bool {
+    let computed = tree.proof.iter().fold(leaf, |node, sibling| match sibling.position {
+        MerkleTreeNodePosition::Left => hash_pair(sibling.value, node),
+        MerkleTreeNodePosition::Right => hash_pair(node, sibling.value),
+    });
+    computed == tree.root
+}
+
+fn height_for(count: usize) -> usize {
+    let height = usize::BITS - (count - 1).leading_zeros();
+    (height as usize).max(1)
+}
+
+fn hash_pair(left: H256, right: H256) -> H256 {
+    let mut buf = [0u8; 64];
+    buf[..32].copy_from_slice(left.as_bytes());
+    buf[32..].copy_from_slice(right.as_bytes());
+    H256::from(keccak256(buf))
+}
+
+#[derive(Debug, Error)]
+pub enum MerkleBatchError {
+    #[error("Merkle batch has no orders")]
+    Empty,
+    #[error("Merkle batch has too many orders (count: {count}, max: {})", 1 << MAX_HEIGHT)]
+    TooManyOrders { count: usize },
+    #[error("Batch order type hash depends on the tree height")]
+    HeightRequired,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::signing::mainnet_domain;
+    use crate::v2::types::QuoteType;
+    use ethers_core::types::U256;
+    use ethers_signers::LocalWallet;
+
+    fn makers(count: u64) -> Vec<Maker> {
+        (0..count)
+            .map(|i| Maker {
+                quote_type: QuoteType::Ask,
+                order_nonce: U256::from(i),
+                collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+                price: U256::exp10(18),
+                item_ids: vec![U256::from(i)],
+                amounts: vec![U256::one()],
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pads_to_power_of_two() {
+        assert_eq!(MerkleOrderBatch::new(makers(1)).unwrap().height(), 1);
+        assert_eq!(MerkleOrderBatch::new(makers(2)).unwrap().height(), 1);
+        assert_eq!(MerkleOrderBatch::new(makers(3)).unwrap().height(), 2);
+        assert_eq!(MerkleOrderBatch::new(makers(1024)).unwrap().height(), 10);
+        assert!(matches!(MerkleOrderBatch::new(makers(1025)), Err(MerkleBatchError::TooManyOrders { .. })));
+        assert!(matches!(MerkleOrderBatch::new(vec![]), Err(MerkleBatchError::Empty)));
+    }
+
+    #[test]
+    fn root_matches_nested_array_encoding() {
+        let makers = makers(3);
+        let batch = MerkleOrderBatch::new(makers.clone()).unwrap();
+
+        let padding = Maker::default().hash();
+        let left = hash_pair(makers[0].hash(), makers[1].hash());
+        let right = hash_pair(makers[2].hash(), padding);
+
+        assert_eq!(batch.root(), hash_pair(left, right));
+    }
+
+    #[test]
+    fn every_order_has_a_valid_proof() {
+        let batch = MerkleOrderBatch::new(makers(7)).unwrap();
+
+        for (index, maker) in batch.makers().iter().enumerate() {
+            let tree = batch.merkle_tree(index).unwrap();
+            assert_eq!(tree.proof.len(), batch.height());
+            assert!(verify_proof(&tree, maker.hash()));
+        }
+
+        assert!(!verify_proof(&batch.merkle_tree(0).unwrap(), batch.makers()[1].hash()));
+        // The eighth leaf is padding, not an order.
+        assert!(batch.merkle_tree(7).is_none());
+        assert!(batch.merkle_tree(8).is_none());
+    }
+
+    #[tokio::test]
+    async fn signature_covers_batch_root() {
+        let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let domain = mainnet_domain();
+        let batch = MerkleOrderBatch::new(makers(5)).unwrap();
+        let digest = batch.typed(domain.clone()).encode_eip712().unwrap();
+
+        let signed = batch.sign(&wallet, domain).await.unwrap();
+        let orders = signed.orders();
+
+        assert_eq!(signed.signature.recover(H256::from(digest)).unwrap(), wallet.address());
+        assert_eq!(orders.len(), 5);
+        assert!(orders.iter().all(|o| o.merkle_tree.as_ref().unwrap().root == signed.batch.root()));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod api;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "contracts")]
+pub mod contracts;
+pub mod merkle;
+pub mod signing;
+pub mod types;
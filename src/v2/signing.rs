@@ -0,0 +1,148 @@
+use crate::constants;
+use crate::v2::types::Maker;
+use ethers_core::{
+    abi::{encode, Token},
+    types::{
+        transaction::eip712::{EIP712Domain, Eip712},
+        Address, Signature, H256, U256,
+    },
+    utils::keccak256,
+};
+use ethers_signers::Signer;
+use std::convert::Infallible;
+
+pub const DOMAIN_NAME: &str = "LooksRareProtocol";
+pub const DOMAIN_VERSION: &str = "2";
+
+pub const MAKER_TYPE: &str = "Maker(uint8 quoteType,uint256 globalNonce,uint256 subsetNonce,uint256 orderNonce,uint256 strategyId,uint8 collectionType,address collection,address currency,address signer,uint256 startTime,uint256 endTime,uint256 price,uint256[] itemIds,uint256[] amounts,bytes additionalParameters)";
+
+/// Signing domain of a `LooksRareProtocol` deployment.
+pub fn domain(chain_id: u64, verifying_contract: Address) -> EIP712Domain {
+    EIP712Domain {
+        name: Some(DOMAIN_NAME.to_string()),
+        version: Some(DOMAIN_VERSION.to_string()),
+        chain_id: Some(U256::from(chain_id)),
+        verifying_contract: Some(verifying_contract),
+        salt: None,
+    }
+}
+
+pub fn mainnet_domain() -> EIP712Domain {
+    domain(1, constants::LOOKSRARE_PROTOCOL_MAINNET.parse().unwrap())
+}
+
+impl Maker {
+    /// EIP-712 `hashStruct` of the order, i.e. the order hash the protocol
+    /// tracks and the leaf used in batch merkle trees.
+    pub fn hash(&self) -> H256 {
+        H256::from(keccak256(encode(&[
+            Token::FixedBytes(keccak256(MAKER_TYPE).to_vec()),
+            Token::Uint(U256::from(u8::from(self.quote_type))),
+            Token::Uint(self.global_nonce),
+            Token::Uint(self.subset_nonce),
+            Token::Uint(self.order_nonce),
+            Token::Uint(U256::from(self.strategy_id)),
+            Token::Uint(U256::from(u8::from(self.collection_type))),
+            Token::Address(self.collection),
+            Token::Address(self.currency),
+            Token::Address(self.signer),
+            Token::Uint(U256::from(self.start_time)),
+            Token::Uint(U256::from(self.end_time)),
+            Token::Uint(self.price),
+            Token::FixedBytes(keccak256(pack_uints(&self.item_ids)).to_vec()),
+            Token::FixedBytes(keccak256(pack_uints(&self.amounts)).to_vec()),
+            Token::FixedBytes(keccak256(&self.additional_parameters).to_vec()),
+        ])))
+    }
+}
+
+/// `abi.encodePacked(uint256[])`: every element padded to a full word.
+pub(crate) fn pack_uints(values: &[U256]) -> Vec<u8> {
+    let mut packed = vec![0u8; values.len() * 32];
+    for (value, word) in values.iter().zip(packed.chunks_mut(32)) {
+        value.to_big_endian(word);
+    }
+    packed
+}
+
+/// A maker order bound to the domain it is signed for.
+#[derive(Clone, Debug)]
+pub struct TypedMaker<'a> {
+    pub maker: &'a Maker,
+    pub domain: EIP712Domain,
+}
+
+impl<'a> Eip712 for TypedMaker<'a> {
+    type Error = Infallible;
+
+    fn domain(&self) -> Result<EIP712Domain, Self::Error> {
+        Ok(self.domain.clone())
+    }
+
+    fn type_hash() -> Result<[u8; 32], Self::Error> {
+        Ok(keccak256(MAKER_TYPE))
+    }
+
+    fn struct_hash(&self) -> Result<[u8; 32], Self::Error> {
+        Ok(self.maker.hash().0)
+    }
+}
+
+/// Signs a single maker order with any ethers [`Signer`], e.g. a
+/// [`SigningKey`](crate::keys::SigningKey).
+pub async fn sign_maker<S: Signer>(
+    signer: &S,
+    maker: &Maker,
+    domain: EIP712Domain,
+) -> Result<Signature, S::Error> {
+    signer.sign_typed_data(&TypedMaker { maker, domain }).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v2::types::{CollectionType, QuoteType};
+    use ethers_signers::LocalWallet;
+
+    fn maker(signer: Address) -> Maker {
+        Maker {
+            quote_type: QuoteType::Ask,
+            global_nonce: U256::zero(),
+            subset_nonce: U256::zero(),
+            order_nonce: U256::from(3),
+            strategy_id: 0,
+            collection_type: CollectionType::ERC721,
+            collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+            currency: Address::zero(),
+            signer,
+            start_time: 1683000000,
+            end_time: 1683086400,
+            price: U256::exp10(18),
+            item_ids: vec![U256::from(62962)],
+            amounts: vec![U256::one()],
+            additional_parameters: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn signature_recovers_maker_signer() {
+        let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let maker = maker(wallet.address());
+        let domain = mainnet_domain();
+
+        let signature = sign_maker(&wallet, &maker, domain.clone()).await.unwrap();
+        let digest = TypedMaker { maker: &maker, domain }.encode_eip712().unwrap();
+
+        assert_eq!(signature.recover(H256::from(digest)).unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn hash_commits_to_every_item() {
+        let base = maker(Address::zero());
+        let mut other = base.clone();
+        other.item_ids.push(U256::from(1));
+        other.amounts.push(U256::one());
+
+        assert_ne!(base.hash(), other.hash());
+    }
+}
@@ -0,0 +1,212 @@
+use crate::types::{u256_dec, u256_dec_vec};
+use serde::{Deserialize, Serialize};
+use ethers_core::types::{Address, Bytes, H256, U256};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum QuoteType {
+    #[default]
+    Bid,
+    Ask,
+}
+
+impl From<QuoteType> for u8 {
+    fn from(quote_type: QuoteType) -> u8 {
+        match quote_type {
+            QuoteType::Bid => 0,
+            QuoteType::Ask => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for QuoteType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(QuoteType::Bid),
+            1 => Ok(QuoteType::Ask),
+            _ => Err(format!("invalid quote type: {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum CollectionType {
+    #[default]
+    ERC721,
+    ERC1155,
+}
+
+impl From<CollectionType> for u8 {
+    fn from(collection_type: CollectionType) -> u8 {
+        match collection_type {
+            CollectionType::ERC721 => 0,
+            CollectionType::ERC1155 => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for CollectionType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CollectionType::ERC721),
+            1 => Ok(CollectionType::ERC1155),
+            _ => Err(format!("invalid collection type: {}", value)),
+        }
+    }
+}
+
+/// Maker order as defined by `OrderStructs.Maker` in the v2 protocol.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Maker {
+    pub quote_type: QuoteType,
+    #[serde(with = "u256_dec")]
+    pub global_nonce: U256,
+    #[serde(with = "u256_dec")]
+    pub subset_nonce: U256,
+    #[serde(with = "u256_dec")]
+    pub order_nonce: U256,
+    pub strategy_id: u64,
+    pub collection_type: CollectionType,
+    pub collection: Address,
+    pub currency: Address,
+    pub signer: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+    #[serde(with = "u256_dec")]
+    pub price: U256,
+    #[serde(with = "u256_dec_vec")]
+    pub item_ids: Vec<U256>,
+    #[serde(with = "u256_dec_vec")]
+    pub amounts: Vec<U256>,
+    pub additional_parameters: Bytes,
+}
+
+/// Taker side of a trade as defined by `OrderStructs.Taker`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Taker {
+    pub recipient: Address,
+    pub additional_parameters: Bytes,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum MerkleTreeNodePosition {
+    #[default]
+    Left,
+    Right,
+}
+
+impl From<MerkleTreeNodePosition> for u8 {
+    fn from(position: MerkleTreeNodePosition) -> u8 {
+        match position {
+            MerkleTreeNodePosition::Left => 0,
+            MerkleTreeNodePosition::Right => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for MerkleTreeNodePosition {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MerkleTreeNodePosition::Left),
+            1 => Ok(MerkleTreeNodePosition::Right),
+            _ => Err(format!("invalid merkle tree node position: {}", value)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleTreeNode {
+    pub value: H256,
+    pub position: MerkleTreeNodePosition,
+}
+
+/// Merkle root and proof for an order signed as part of a batch. Orders
+/// signed on their own carry an empty tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleTree {
+    pub root: H256,
+    pub proof: Vec<MerkleTreeNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub id: String,
+    pub hash: H256,
+    pub quote_type: QuoteType,
+    #[serde(with = "u256_dec")]
+    pub global_nonce: U256,
+    #[serde(with = "u256_dec")]
+    pub subset_nonce: U256,
+    #[serde(with = "u256_dec")]
+    pub order_nonce: U256,
+    pub strategy_id: u64,
+    pub collection_type: CollectionType,
+    pub collection: Address,
+    pub currency: Address,
+    pub signer: Address,
+    pub start_time: u64,
+    pub end_time: u64,
+    #[serde(with = "u256_dec")]
+    pub price: U256,
+    #[serde(with = "u256_dec_vec")]
+    pub item_ids: Vec<U256>,
+    #[serde(with = "u256_dec_vec")]
+    pub amounts: Vec<U256>,
+    pub additional_parameters: Bytes,
+    pub signature: Bytes,
+    pub merkle_root: Option<H256>,
+    pub merkle_proof: Option<Vec<MerkleTreeNode>>,
+    pub status: String,
+}
+
+impl Order {
+    pub fn maker(&self) -> Maker {
+        Maker {
+            quote_type: self.quote_type,
+            global_nonce: self.global_nonce,
+            subset_nonce: self.subset_nonce,
+            order_nonce: self.order_nonce,
+            strategy_id: self.strategy_id,
+            collection_type: self.collection_type,
+            collection: self.collection,
+            currency: self.currency,
+            signer: self.signer,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            price: self.price,
+            item_ids: self.item_ids.clone(),
+            amounts: self.amounts.clone(),
+            additional_parameters: self.additional_parameters.clone(),
+        }
+    }
+
+    pub fn merkle_tree(&self) -> MerkleTree {
+        match (self.merkle_root, &self.merkle_proof) {
+            (Some(root), Some(proof)) => MerkleTree { root, proof: proof.clone() },
+            _ => MerkleTree::default(),
+        }
+    }
+}
+
+/// Bid and ask nonces the protocol currently expects from a signer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserNonces {
+    #[serde(with = "u256_dec")]
+    pub ask_nonce: U256,
+    #[serde(with = "u256_dec")]
+    pub bid_nonce: U256,
+}
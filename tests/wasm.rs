@@ -0,0 +1,59 @@
+//! Runs in a headless browser: `cargo test-wasm` (see README).
+#![cfg(target_arch = "wasm32")]
+
+use async_trait::async_trait;
+use ethers_core::types::{transaction::eip712::Eip712, Address, H256, U256};
+use ethers_signers::{LocalWallet, Signer};
+use looksrare::{
+    api::LooksRareApi,
+    transport::{HttpRequest, HttpResponse, Transport, TransportError},
+    v2::{
+        signing::{mainnet_domain, sign_maker, TypedMaker},
+        types::{Maker, QuoteType},
+    },
+};
+use reqwest::StatusCode;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct ReplayTransport;
+
+#[async_trait(?Send)]
+impl Transport for ReplayTransport {
+    async fn send(&self, _req: HttpRequest) -> Result<HttpResponse, TransportError> {
+        Ok(HttpResponse {
+            status: StatusCode::OK,
+            headers: Default::default(),
+            body: include_str!("../fixtures/v1/orders_nonce.json").to_string(),
+        })
+    }
+}
+
+#[wasm_bindgen_test]
+async fn signs_maker_orders() {
+    let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+    let maker = Maker {
+        quote_type: QuoteType::Ask,
+        collection: "0x34d85c9cdeb23fa97cb08333b511ac86e1c4e258".parse().unwrap(),
+        signer: wallet.address(),
+        price: U256::exp10(18),
+        item_ids: vec![U256::from(62962)],
+        amounts: vec![U256::one()],
+        ..Default::default()
+    };
+    let domain = mainnet_domain();
+
+    let signature = sign_maker(&wallet, &maker, domain.clone()).await.unwrap();
+    let digest = TypedMaker { maker: &maker, domain }.encode_eip712().unwrap();
+
+    assert_eq!(signature.recover(H256::from(digest)).unwrap(), wallet.address());
+}
+
+#[wasm_bindgen_test]
+async fn api_runs_on_custom_transport() {
+    let api = LooksRareApi::builder().transport(ReplayTransport).build();
+
+    let address: Address = "0x3d67b76CF3dcc881255eb2262E788BE03b2f5B9F".parse().unwrap();
+    assert_eq!(api.get_nonce(address).await.unwrap(), 17833);
+}